@@ -0,0 +1,148 @@
+use crate::CachedScore; //CachedScore struct
+use crate::imports::*; //Imports
+use crate::datatweaks::{create_db, fetch_cached_scores, fetch_player_data, sync_score_cache};
+use crate::message::send_simple_format; //Function to send a simple embedded message
+use crate::locale::{self, Locale};
+use crate::theme; //Embed themes
+
+const BUCKET_SIZE: f64 = 1.0; //Stars in each bucket
+const MIN_SCORES: usize = 3; //Buckets with fewer scores are not flagged (too few to tell)
+const UNDER_BY: f64 = 1.0; //Percentage points under the overall accuracy that count as under-performing
+
+//Accuracy of the scores in one star range
+struct Bucket {
+    min: f64, //Lowest stars of the bucket
+    count: usize,
+    average: f64,
+    median: f64,
+    best: f64,
+}
+
+//Function to bucket the accuracies (in percent) of ranked scores by star rating
+fn bucket_scores(scores: &[CachedScore]) -> Vec<Bucket> {
+    let mut accuracies: HashMap<i64, Vec<f64>> = HashMap::new();
+    for score in scores.iter().filter(|score| score.pp > 0.0) { //Only ranked scores
+        if let Some(acc) = score.accuracy() {
+            let bucket = (score.stars / BUCKET_SIZE).floor() as i64;
+            accuracies.entry(bucket).or_default().push(acc * 100.0);
+        }
+    }
+    let mut buckets: Vec<Bucket> = accuracies.into_iter().map(|(bucket, mut accs)| {
+        accs.sort_by(|a, b| a.total_cmp(b));
+        let middle = accs.len() / 2;
+        let median = if accs.len() % 2 == 0 { (accs[middle - 1] + accs[middle]) / 2.0 } else { accs[middle] };
+        Bucket {
+            min: bucket as f64 * BUCKET_SIZE,
+            count: accs.len(),
+            average: accs.iter().sum::<f64>() / accs.len() as f64,
+            median,
+            best: accs[accs.len() - 1],
+        }
+    }).collect();
+    buckets.sort_by(|a, b| a.min.total_cmp(&b.min));
+    buckets
+}
+
+//Function to check if a bucket is under the overall accuracy of the player
+fn under_performing(bucket: &Bucket, overall: f64) -> bool {
+    bucket.count >= MIN_SCORES && bucket.average < overall - UNDER_BY
+}
+
+//Function to make the accuracy table (one row per bucket, flagged rows are marked)
+fn format_table(buckets: &[Bucket], overall: f64, locale: Locale) -> String {
+    let header: Vec<&str> = locale.text("acc_profile.header").split('|').collect(); //Column names
+    let mut table = format!("{:<9} {:>8} {:>6} {:>7} {:>6}\n", header[0], header[1], header[2], header[3], header[4]);
+    for bucket in buckets {
+        table += &format!(
+            "{:<9} {:>8} {:>6} {:>7} {:>6}{}\n",
+            format!("{}-{}", bucket.min, bucket.min + BUCKET_SIZE), bucket.count, locale.decimal(bucket.average, 2),
+            locale.decimal(bucket.median, 2), locale.decimal(bucket.best, 2), if under_performing(bucket, overall) { " <" } else { "" }
+        );
+    }
+    format!("```\n{}```", table)
+}
+
+//Function to send the accuracy of a player by star rating
+pub async fn send_acc_profile(ctx: &Context, msg: &Message, player_id: &str) {
+    let locale = locale::for_message(msg); //Language and number format
+    let data = match fetch_player_data(player_id).await { //Fetch data from ScoreSaber API
+        Ok(data) => data,
+        Err(e) => {
+            println!("Error fetching data from API: {}", e); //Print error
+            send_simple_format(ctx, msg, format!("{} (`!acc-profile` `player_id`)", locale.text("error.invalid_id")).as_str()).await; //Send error
+            return;
+        }
+    };
+    if !sync_score_cache(&data.id).await { //Bring the score cache up to date
+        send_simple_format(ctx, msg, locale.text("error.fetch_scores")).await; //Send error
+        return;
+    }
+    let scores = {
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        create_db(&conn).and_then(|_| fetch_cached_scores(&conn, &data.id))
+    };
+    let buckets = match scores {
+        Ok(scores) => bucket_scores(&scores),
+        Err(e) => {
+            println!("Error fetching cached scores: {}", e); //Print error
+            send_simple_format(ctx, msg, locale.text("error.load_scores")).await; //Send error
+            return;
+        }
+    };
+    if buckets.is_empty() {
+        send_simple_format(ctx, msg, locale.fill("acc_profile.none", &[&data.name]).as_str()).await; //Send error
+        return;
+    }
+
+    let overall = data.scoreStats.averageRankedAccuracy;
+    let flagged: Vec<String> = buckets.iter().filter(|bucket| under_performing(bucket, overall)).map(|bucket| {
+        locale.fill("acc_profile.below", &[&bucket.min.to_string(), &(bucket.min + BUCKET_SIZE).to_string(), &locale.decimal(overall - bucket.average, 2)])
+    }).collect();
+    let embed = CreateEmbed::new() //Make Embed
+        .color(theme::for_message(msg).color) //Set color to the theme
+        .title(locale.fill("acc_profile.title", &[&data.name])) //Set title
+        .description(format_table(&buckets, overall, locale)) //Set description to the table
+        .field(locale.text("metric.acc"), format!("{}{}", locale.decimal(overall, 2), locale.unit("%")), true)
+        .field(locale.text("acc_profile.under"), if flagged.is_empty() { locale.text("acc_profile.consistent").to_string() } else { flagged.join("\n") }, false)
+        .footer(CreateEmbedFooter::new(locale.fill("acc_profile.footer", &[&UNDER_BY.to_string(), &MIN_SCORES.to_string()])));
+    if let Err(why) = msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await { // If sending message has error
+        println!("Error sending message: {:?}", why); // Print error
+    }
+    println!("Accuracy profile of `{}` sent to Discord (!acc-profile)", data.name); //Print success
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //Function to make a cached score with an accuracy in percent
+    fn score(stars: f64, acc: i64, pp: f64) -> CachedScore {
+        CachedScore { stars, pp, baseScore: acc * 1000, maxScore: 100_000, ..Default::default() }
+    }
+
+    //Function to compare floats that went through some math
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn bucket_scores_groups_ranked_scores_by_stars() {
+        let scores = vec![
+            score(5.2, 90, 200.0), score(5.9, 96, 250.0), score(5.0, 94, 210.0), score(5.5, 92, 220.0),
+            score(6.5, 80, 230.0),
+            score(7.0, 99, 0.0), //Unranked
+            CachedScore { stars: 8.0, pp: 100.0, baseScore: 1000, maxScore: 0, ..Default::default() }, //No max score
+        ];
+        let buckets = bucket_scores(&scores);
+        assert_eq!(buckets.len(), 2);
+        assert!(close(buckets[0].min, 5.0));
+        assert_eq!(buckets[0].count, 4);
+        assert!(close(buckets[0].average, 93.0));
+        assert!(close(buckets[0].median, 93.0)); //Even count takes the middle two
+        assert!(close(buckets[0].best, 96.0));
+        assert!(close(buckets[1].min, 6.0));
+        assert_eq!(buckets[1].count, 1);
+        assert!(close(buckets[1].median, 80.0));
+        assert!(bucket_scores(&[]).is_empty());
+    }
+}
@@ -0,0 +1,323 @@
+use crate::PlayerData; //PlayerData struct
+use crate::imports::*; //Imports
+use crate::datatweaks::{create_db, current_time, fetch_player_data, fetch_player_scores, fetch_reminders, fetch_score_times, fetch_snapshots_since, remove_reminder, set_reminded, set_reminder, sync_score_cache};
+use crate::card::{draw_text, fill_rect, render_blocking, theme_pixel}; //Drawing helpers of the profile card
+use crate::message::send_simple_format; //Function to send a simple embedded message
+use crate::locale::{self, Locale};
+use crate::theme; //Embed themes
+use chrono::{Datelike, Days, NaiveTime};
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use std::collections::BTreeMap; //Days stay in order
+
+const DAY: i64 = 86_400; //Seconds in a day
+const WEEKS: u32 = 53; //Weeks shown on the calendar
+const CELL: u32 = 12; //Size of one day on the calendar
+const GAP: u32 = 3; //Space between the days
+const MARGIN: u32 = 16; //Space around the calendar
+const TOP: u32 = 44; //Space for the title and the months
+const BACKGROUND: Rgba<u8> = Rgba([24, 24, 28, 255]);
+const EMPTY: Rgba<u8> = Rgba([44, 44, 52, 255]);
+const TEXT: Rgba<u8> = Rgba([235, 235, 235, 255]);
+const MUTED: Rgba<u8> = Rgba([150, 150, 160, 255]);
+const DEFAULT_REMIND_DAYS: i64 = 3; //Days without a score before reminding if none are given
+const MAX_REMIND_DAYS: i64 = 30;
+
+//Function to find the day (UTC) of a timestamp
+fn day_of(time: i64) -> NaiveDate {
+    DateTime::<Utc>::from_timestamp(time, 0).map(|date| date.date_naive()).unwrap_or_default()
+}
+
+//Function to count the plays of a player per day (play count from the snapshots, scores from the score cache, whichever is higher)
+fn daily_plays(conn: &Connection, player_id: &str) -> Result<BTreeMap<NaiveDate, i64>> {
+    let mut from_snapshots: BTreeMap<NaiveDate, i64> = BTreeMap::new();
+    let snapshots = fetch_snapshots_since(conn, player_id, 0)?;
+    for pair in snapshots.windows(2) { //Plays between two snapshots count on the day of the later one
+        let plays = pair[1].totalPlayCount - pair[0].totalPlayCount;
+        if plays > 0 {
+            *from_snapshots.entry(day_of(pair[1].timestamp)).or_default() += plays;
+        }
+    }
+    let mut days: BTreeMap<NaiveDate, i64> = BTreeMap::new();
+    for time in fetch_score_times(conn, player_id)? {
+        if let Ok(time) = DateTime::parse_from_rfc3339(&time) {
+            *days.entry(time.date_naive()).or_default() += 1;
+        }
+    }
+    for (day, plays) in from_snapshots {
+        let entry = days.entry(day).or_default();
+        *entry = (*entry).max(plays);
+    }
+    Ok(days)
+}
+
+//Function to find the current and longest streak of days with plays (today not being played yet doesn't break the streak)
+fn streaks(days: &BTreeMap<NaiveDate, i64>, today: NaiveDate) -> (i64, i64) {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for day in days.keys() { //Days are in order
+        run = if previous.is_some_and(|previous| previous.succ_opt() == Some(*day)) { run + 1 } else { 1 };
+        longest = longest.max(run);
+        previous = Some(*day);
+    }
+    let mut current = 0;
+    let mut day = if days.contains_key(&today) { Some(today) } else { today.pred_opt() };
+    while let Some(played) = day.filter(|day| days.contains_key(day)) { //Count back while every day has plays
+        current += 1;
+        day = played.pred_opt();
+    }
+    (current, longest)
+}
+
+//Function to load the daily plays of a player after bringing the score cache up to date (None if the player is invalid)
+async fn load_activity(ctx: &Context, msg: &Message, player_id: &str, command: &str, locale: Locale) -> Option<(PlayerData, BTreeMap<NaiveDate, i64>)> {
+    let data = match fetch_player_data(player_id).await { //Fetch data from ScoreSaber API
+        Ok(data) => data,
+        Err(e) => {
+            println!("Error fetching data from API: {}", e); //Print error
+            send_simple_format(ctx, msg, format!("{} (`{}` `player_id`)", locale.text("error.invalid_id"), command).as_str()).await; //Send error
+            return None;
+        }
+    };
+    if !sync_score_cache(&data.id).await { //Bring the score cache up to date
+        send_simple_format(ctx, msg, locale.text("error.fetch_scores")).await; //Send error
+        return None;
+    }
+    let days = {
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        create_db(&conn).and_then(|_| daily_plays(&conn, &data.id))
+    };
+    match days {
+        Ok(days) => Some((data, days)),
+        Err(e) => {
+            println!("Error fetching activity from database: {}", e); //Print error
+            send_simple_format(ctx, msg, locale.text("activity.failed")).await; //Send error
+            None
+        }
+    }
+}
+
+//Function to send the current and longest play streak of a player
+pub async fn send_streak(ctx: &Context, msg: &Message, player_id: &str) {
+    let locale = locale::for_message(msg); //Language and number format
+    let (data, days) = match load_activity(ctx, msg, player_id, "!streak", locale).await {
+        Some(activity) => activity,
+        None => return,
+    };
+    let today = day_of(current_time());
+    let (current, longest) = streaks(&days, today);
+    let month = today.checked_sub_days(Days::new(29)).unwrap_or(today); //Today and the 29 days before
+    let active = days.range(month..).count() as i64; //Days with plays in the last 30 days
+    let last = days.keys().next_back().map(|day| locale.date(day.and_time(NaiveTime::MIN).and_utc().timestamp())).unwrap_or(locale.text("streak.never").to_string());
+    let days_word = |count: i64| format!("{}{}", locale.integer(count, false), locale.text(if count == 1 { "streak.day" } else { "streak.days" }));
+    let embed = CreateEmbed::new() //Make Embed
+        .color(theme::for_message(msg).color) //Set color to the theme
+        .title(locale.fill("streak.title", &[&data.name])) //Set title
+        .field(locale.text("streak.current"), days_word(current), true)
+        .field(locale.text("streak.longest"), days_word(longest), true)
+        .field(locale.text("streak.active"), format!("{}/30", active), true)
+        .field(locale.text("streak.last"), last, true)
+        .footer(CreateEmbedFooter::new(locale.text("streak.footer")));
+    if let Err(why) = msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await { // If sending message has error
+        println!("Error sending message: {:?}", why); // Print error
+    }
+    println!("Play streak of `{}` sent to Discord (!streak)", data.name); //Print success
+}
+
+//Function to blend the empty color into the accent (level 1 to 4)
+fn level_color(accent: Rgba<u8>, level: u32) -> Rgba<u8> {
+    let blend = |empty: u8, accent: u8| (empty as i32 + (accent as i32 - empty as i32) * level as i32 / 4) as u8;
+    Rgba([blend(EMPTY[0], accent[0]), blend(EMPTY[1], accent[1]), blend(EMPTY[2], accent[2]), 255])
+}
+
+//Function to render the calendar of the last year (one column per week, Monday on top)
+fn render_calendar(name: &str, days: &BTreeMap<NaiveDate, i64>, today: NaiveDate, accent: Rgba<u8>, locale: Locale) -> Result<Vec<u8>, image::ImageError> {
+    let width = MARGIN * 2 + WEEKS * (CELL + GAP);
+    let height = TOP + 7 * (CELL + GAP) + MARGIN + 20;
+    let mut img = RgbaImage::from_pixel(width, height, BACKGROUND);
+    let start = today.checked_sub_days(Days::new(((WEEKS - 1) * 7 + today.weekday().num_days_from_monday()) as u64)).unwrap_or(today); //Monday of the first week
+    let most = days.range(start..).map(|(_, plays)| *plays).max().unwrap_or(0).max(1);
+    let total: i64 = days.range(start..).map(|(_, plays)| *plays).sum();
+
+    draw_text(&mut img, MARGIN as i64, 10, &locale.fill("calendar.title", &[name, &locale.integer(total, false)]), 1, TEXT);
+    let months: Vec<&str> = locale.text("calendar.months").split('|').collect(); //Month labels (January first)
+    let mut day = start;
+    while day <= today { //Draw every day
+        let column = (day - start).num_days() as u32 / 7;
+        let row = day.weekday().num_days_from_monday();
+        let x = (MARGIN + column * (CELL + GAP)) as i64;
+        if day.day() == 1 { //Month labels above the week each month starts in
+            draw_text(&mut img, x, 26, months[day.month0() as usize], 1, MUTED);
+        }
+        let plays = days.get(&day).copied().unwrap_or(0);
+        let color = if plays > 0 { level_color(accent, ((plays * 4 + most - 1) / most).clamp(1, 4) as u32) } else { EMPTY };
+        fill_rect(&mut img, x, (TOP + row * (CELL + GAP)) as i64, CELL, CELL, color);
+        day = match day.succ_opt() {
+            Some(next) => next,
+            None => break,
+        };
+    }
+
+    //Legend
+    let legend_y = (TOP + 7 * (CELL + GAP) + 6) as i64;
+    let (less, more) = (locale.text("calendar.less"), locale.text("calendar.more"));
+    let boxes_x = width as i64 - MARGIN as i64 - 5 * (CELL + GAP) as i64 - more.len() as i64 * 8 - 4; //Labels are 8 pixels per character
+    draw_text(&mut img, boxes_x - less.len() as i64 * 8 - 4, legend_y + 2, less, 1, MUTED);
+    for level in 0..5 {
+        let color = if level == 0 { EMPTY } else { level_color(accent, level) };
+        fill_rect(&mut img, boxes_x + (level * (CELL + GAP)) as i64, legend_y, CELL, CELL, color);
+    }
+    draw_text(&mut img, boxes_x + 4 + 5 * (CELL + GAP) as i64, legend_y + 2, more, 1, MUTED);
+
+    //Encode as PNG
+    let mut bytes = Vec::new();
+    DynamicImage::ImageRgba8(img).write_to(&mut std::io::Cursor::new(&mut bytes), ImageFormat::Png)?;
+    Ok(bytes)
+}
+
+//Function to send the play calendar (heatmap of plays per day) of a player
+pub async fn send_calendar(ctx: &Context, msg: &Message, player_id: &str) {
+    let locale = locale::for_message(msg); //Language and number format
+    let (data, days) = match load_activity(ctx, msg, player_id, "!calendar", locale).await {
+        Some(activity) => activity,
+        None => return,
+    };
+    let accent = theme_pixel(theme::for_message(msg).color); //Colors of the calendar follow the theme
+    let name = data.name.clone();
+    match render_blocking(move || render_calendar(&name, &days, day_of(current_time()), accent, locale)).await {
+        Ok(png) => {
+            let message = CreateMessage::new().add_file(CreateAttachment::bytes(png, format!("{}-calendar.png", data.id)));
+            if let Err(why) = msg.channel_id.send_message(&ctx.http, message).await { // If sending message has error
+                println!("Error sending message: {:?}", why); // Print error
+            } else {
+                println!("Play calendar of `{}` sent to Discord (!calendar)", data.name); //Print success
+            }
+        }
+        Err(e) => {
+            println!("Error rendering calendar: {}", e); //Print error
+            send_simple_format(ctx, msg, locale.text("calendar.failed")).await; //Send error
+        }
+    }
+}
+
+//Function to opt in or out of inactivity reminders (parts are on + days or off)
+pub async fn remind_command(ctx: &Context, msg: &Message, parts: &[&str], linked_id: &str) {
+    let locale = locale::for_message(msg); //Language of the replies
+    if linked_id.is_empty() { //Reminders are for linked players
+        send_simple_format(ctx, msg, format!("{} (`!link` `player_id`)", locale.text("remind.link_first")).as_str()).await; //Send error
+        return;
+    }
+    let discord_id = msg.author.name.as_str();
+    match parts.first().copied().unwrap_or("") {
+        "on" => {
+            let days = match parts.get(1).map(|days| days.parse::<i64>()) {
+                None => DEFAULT_REMIND_DAYS,
+                Some(Ok(days)) if (1..=MAX_REMIND_DAYS).contains(&days) => days,
+                _ => {
+                    send_simple_format(ctx, msg, format!("{} (`!remind` `on` `days [OPTIONAL]`)", locale.fill("remind.invalid_days", &[&MAX_REMIND_DAYS.to_string()])).as_str()).await; //Send error
+                    return;
+                }
+            };
+            let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+            let result = create_db(&conn).and_then(|_| set_reminder(&conn, discord_id, &msg.author.id.to_string(), &msg.channel_id.to_string(), days));
+            drop(conn); //Close database before sending
+            match result {
+                Ok(()) => {
+                    println!("Discord `{}` opted into reminders after {} days (!remind)", discord_id, days); //Print success
+                    send_simple_format(ctx, msg, format!("{} (`!remind` `off`)", locale.fill("remind.on", &[&days.to_string()])).as_str()).await; //Send success
+                }
+                Err(e) => {
+                    println!("Error setting reminder: {}", e); //Print error
+                    send_simple_format(ctx, msg, locale.text("remind.set_failed")).await; //Send error
+                }
+            }
+        }
+        _ => { //Off
+            let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+            let result = create_db(&conn).and_then(|_| remove_reminder(&conn, discord_id));
+            drop(conn); //Close database before sending
+            match result {
+                Ok(true) => send_simple_format(ctx, msg, locale.text("remind.off")).await, //Send success
+                Ok(false) => send_simple_format(ctx, msg, format!("{} (`!remind` `on` `days [OPTIONAL]`)", locale.text("remind.not_on")).as_str()).await, //Send error
+                Err(e) => {
+                    println!("Error removing reminder: {}", e); //Print error
+                    send_simple_format(ctx, msg, locale.text("remind.remove_failed")).await; //Send error
+                }
+            }
+        }
+    }
+}
+
+//Function to remind every opted in player that hasn't set a score for their amount of days (once per break)
+pub async fn send_inactivity_reminders(ctx: &Context) {
+    let reminders = {
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        create_db(&conn).and_then(|_| fetch_reminders(&conn))
+    };
+    let reminders = match reminders {
+        Ok(reminders) => reminders,
+        Err(e) => {
+            println!("Error fetching reminders from database: {}", e); //Print error
+            return;
+        }
+    };
+    let now = current_time();
+    for reminder in reminders { //Loop through every opted in discord
+        let last_played = match fetch_player_scores(&reminder.player, "recent", 1, 1).await { //Newest score of the player
+            Ok(recent) => recent.playerScores.first().and_then(|score| DateTime::parse_from_rfc3339(&score.score.timeSet).ok()).map(|time| time.timestamp()).unwrap_or(0),
+            Err(e) => {
+                println!("Error fetching recent scores from API: {}", e); //Print error
+                continue;
+            }
+        };
+        if now - last_played < reminder.days * DAY || reminder.reminded > last_played { //Still active or already reminded for this break
+            continue;
+        }
+        let channel = match reminder.channel.parse::<u64>() { //If the stored channel is valid
+            Ok(channel) => ChannelId::new(channel),
+            Err(_) => continue,
+        };
+        let locale: Locale = locale::resolve(Some(&reminder.discord), None); //Language and number format of the discord
+        let days = (now - last_played) / DAY;
+        let text = if last_played == 0 {
+            locale.text("remind.never_played").to_string()
+        } else {
+            format!("{} (`!remind` `off`)", locale.fill("remind.inactive", &[&locale.integer(days, false)]))
+        };
+        let embed = CreateEmbed::new().color(theme::resolve(Some(&reminder.discord), None).accent).description(text);
+        let message = CreateMessage::new().content(format!("<@{}>", reminder.user)).embed(embed); //Mentions in embeds don't notify
+        if let Err(why) = channel.send_message(&ctx.http, message).await { // If sending message has error
+            println!("Error sending reminder: {:?}", why); // Print error
+            continue;
+        }
+        println!("Inactivity reminder sent to Discord [Discord: {}]", reminder.discord); // Prints if successful
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        if let Err(e) = set_reminded(&conn, &reminder.discord, now) { //Remember the reminder was sent
+            println!("Error updating reminder: {}", e); //Print error
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //Function to make a day of October 2026
+    fn october(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    //Function to mark days as played
+    fn played(days: &[u32]) -> BTreeMap<NaiveDate, i64> {
+        days.iter().map(|day| (october(*day), 1)).collect()
+    }
+
+    #[test]
+    fn streaks_count_current_and_longest_runs() {
+        assert_eq!(streaks(&BTreeMap::new(), october(19)), (0, 0));
+        assert_eq!(streaks(&played(&[1, 2, 3, 4, 5, 17, 18]), october(19)), (2, 5)); //Today not played yet
+        assert_eq!(streaks(&played(&[1, 2, 3, 4, 5, 17, 18, 19]), october(19)), (3, 5));
+        assert_eq!(streaks(&played(&[10, 11, 12, 16]), october(19)), (0, 3)); //Missed yesterday
+        assert_eq!(streaks(&played(&[19]), october(19)), (1, 1));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::PlayerData; //PlayerData struct
+use crate::imports::*; //Imports
+use crate::datatweaks::{fetch_bytes, fetch_player_data, fetch_player_scores};
+use crate::format::difficulty_name; //Function to name a difficulty
+use crate::locale::{self, Locale};
+use crate::message::send_simple_format; //Function to send a simple embedded message
+use crate::theme; //Embed themes
+use font8x8::UnicodeFonts; //Used to look up the glyphs of the bitmap font
+use image::{imageops, DynamicImage, ImageFormat, Rgba, RgbaImage};
+
+const WIDTH: u32 = 640; //Width of the card
+const HEIGHT: u32 = 240; //Height of the card
+const AVATAR: u32 = 128; //Size of the avatar
+const BACKGROUND: Rgba<u8> = Rgba([24, 24, 28, 255]);
+const TEXT: Rgba<u8> = Rgba([235, 235, 235, 255]);
+const MUTED: Rgba<u8> = Rgba([150, 150, 160, 255]);
+
+//Function to turn a theme color into a pixel (black themes use a light gray so the accent is visible)
+pub fn theme_pixel(color: u32) -> Rgba<u8> {
+    if color == 0 {
+        return Rgba([200, 200, 200, 255]);
+    }
+    Rgba([(color >> 16) as u8, (color >> 8) as u8, color as u8, 255])
+}
+
+//Function to fill a rectangle (clipped to the image)
+pub fn fill_rect(img: &mut RgbaImage, x: i64, y: i64, width: u32, height: u32, color: Rgba<u8>) {
+    for py in y.max(0)..(y + height as i64).min(img.height() as i64) {
+        for px in x.max(0)..(x + width as i64).min(img.width() as i64) {
+            img.put_pixel(px as u32, py as u32, color);
+        }
+    }
+}
+
+//Function to draw text with the 8x8 bitmap font (scale 1 is 8 pixels tall)
+pub fn draw_text(img: &mut RgbaImage, x: i64, y: i64, text: &str, scale: u32, color: Rgba<u8>) {
+    for (index, character) in text.chars().enumerate() { //Draw every character
+        let glyph = font8x8::BASIC_FONTS.get(character).or_else(|| font8x8::BASIC_FONTS.get('?')).unwrap_or([0; 8]); //Unknown characters become ?
+        let left = x + (index as u32 * 8 * scale) as i64;
+        for (row, bits) in glyph.iter().enumerate() {
+            for column in 0..8 {
+                if bits & (1 << column) != 0 { //If the pixel of the glyph is set
+                    fill_rect(img, left + (column * scale) as i64, y + (row as u32 * scale) as i64, scale, scale, color);
+                }
+            }
+        }
+    }
+}
+
+//Function to cut text so it fits in a width (in pixels)
+pub fn fit_text(text: &str, width: u32, scale: u32) -> String {
+    let max = (width / (8 * scale)) as usize;
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    format!("{}..", text.chars().take(max.saturating_sub(2)).collect::<String>())
+}
+
+//Function to draw a line (a few pixels thick)
+pub fn draw_line(img: &mut RgbaImage, from: (i64, i64), to: (i64, i64), color: Rgba<u8>) {
+    let steps = (to.0 - from.0).abs().max((to.1 - from.1).abs()).max(1);
+    for step in 0..=steps { //Walk along the line
+        let x = from.0 + (to.0 - from.0) * step / steps;
+        let y = from.1 + (to.1 - from.1) * step / steps;
+        fill_rect(img, x - 1, y - 1, 2, 2, color);
+    }
+}
+
+//Function to draw the rank history (histories from ScoreSaber plus the current rank) as a sparkline
+fn draw_sparkline(img: &mut RgbaImage, data: &PlayerData, at: (i64, i64), width: u32, height: u32, color: Rgba<u8>, locale: Locale) {
+    let (x, y) = at;
+    let mut ranks: Vec<i64> = data.histories.split(',').filter_map(|rank| rank.trim().parse::<i64>().ok()).filter(|rank| *rank > 0).collect();
+    if data.rank > 0 {
+        ranks.push(data.rank);
+    }
+    fill_rect(img, x, y, width, height, Rgba([34, 34, 40, 255])); //Background of the sparkline
+    draw_text(img, x + 6, y + 6, locale.text("card.rank_history"), 1, MUTED);
+    if ranks.len() < 2 { //Not enough history to draw
+        draw_text(img, x + 6, y + height as i64 / 2, locale.text("card.not_enough_data"), 1, MUTED);
+        return;
+    }
+    let best = *ranks.iter().min().unwrap_or(&1);
+    let worst = *ranks.iter().max().unwrap_or(&1);
+    let top = y + 22; //Leave room for the label
+    let bottom = y + height as i64 - 8;
+    let point = |index: usize, rank: i64| {
+        let px = x + 6 + (index as i64 * (width as i64 - 12)) / (ranks.len() as i64 - 1);
+        let py = if worst == best { (top + bottom) / 2 } else { top + (rank - best) * (bottom - top) / (worst - best) }; //Better ranks are higher up
+        (px, py)
+    };
+    for index in 1..ranks.len() { //Connect every point
+        draw_line(img, point(index - 1, ranks[index - 1]), point(index, ranks[index]), color);
+    }
+}
+
+//Function to run an image rendering on the blocking thread pool (decoding, resizing and encoding would stall the bot)
+pub async fn render_blocking<F>(render: F) -> Result<Vec<u8>, image::ImageError>
+where
+    F: FnOnce() -> Result<Vec<u8>, image::ImageError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(render).await.unwrap_or_else(|e| Err(image::ImageError::IoError(std::io::Error::other(e)))) //A panicked render is an error
+}
+
+//Function to draw the profile card of a player as a PNG (avatar and flag are the downloaded images)
+fn draw_card(data: &PlayerData, avatar: Option<Vec<u8>>, flag: Option<Vec<u8>>, top_play: Option<String>, accent: Rgba<u8>, locale: Locale) -> Result<Vec<u8>, image::ImageError> {
+    let mut img = RgbaImage::from_pixel(WIDTH, HEIGHT, BACKGROUND);
+    fill_rect(&mut img, 0, 0, 8, HEIGHT, accent); //Accent stripe
+
+    //Avatar (gray square if it can't be downloaded)
+    match avatar.and_then(|bytes| image::load_from_memory(&bytes).ok()) {
+        Some(avatar) => imageops::overlay(&mut img, &avatar.resize_exact(AVATAR, AVATAR, imageops::FilterType::Triangle).to_rgba8(), 24, 24),
+        None => fill_rect(&mut img, 24, 24, AVATAR, AVATAR, MUTED),
+    }
+
+    //Country flag and code
+    if let Some(flag) = flag.and_then(|bytes| image::load_from_memory(&bytes).ok()) {
+        imageops::overlay(&mut img, &flag.resize(32, 24, imageops::FilterType::Triangle).to_rgba8(), 24, 168);
+    }
+    draw_text(&mut img, 64, 172, &data.country, 2, TEXT);
+
+    //Name and stats
+    draw_text(&mut img, 176, 24, &fit_text(&data.name, WIDTH - 200, 3), 3, TEXT);
+    draw_text(&mut img, 176, 68, &format!("#{} ({} #{})", locale.integer(data.rank, false), data.country, locale.integer(data.countryRank, false)), 2, accent);
+    draw_text(&mut img, 176, 96, &format!("{}pp", locale.decimal(data.pp, 2)), 2, TEXT);
+    draw_text(&mut img, 176, 124, &locale.fill("card.acc", &[&format!("{}{}", locale.decimal(data.scoreStats.averageRankedAccuracy, 2), locale.unit("%"))]), 2, TEXT);
+    draw_sparkline(&mut img, data, (400, 64), 216, 110, accent, locale);
+
+    //Top play
+    draw_text(&mut img, 24, 208, &fit_text(&top_play.unwrap_or(locale.text("card.no_top_play").to_string()), WIDTH - 48, 2), 2, MUTED);
+
+    //Encode as PNG
+    let mut bytes = Vec::new();
+    DynamicImage::ImageRgba8(img).write_to(&mut std::io::Cursor::new(&mut bytes), ImageFormat::Png)?;
+    Ok(bytes)
+}
+
+//Function to render the profile card of a player as a PNG (downloads first, then draws on the blocking thread pool)
+async fn render_card(data: &PlayerData, accent: Rgba<u8>, locale: Locale) -> Result<Vec<u8>, image::ImageError> {
+    let avatar = fetch_bytes(&data.profilePicture).await.ok();
+    let flag_url = format!("https://scoresaber.com/imports/images/flags/{}.png", data.country.to_lowercase());
+    let flag = fetch_bytes(&flag_url).await.ok();
+    let top_play = match fetch_player_scores(&data.id, "top", 1, 1).await {
+        Ok(scores) => scores.playerScores.first().map(|top| {
+            locale.fill("card.top_play", &[&top.leaderboard.songName, difficulty_name(top.leaderboard.difficulty.difficulty), &locale.decimal(top.score.pp, 2)])
+        }),
+        Err(e) => {
+            println!("Error fetching top play from API: {}", e); //Print error
+            None
+        }
+    };
+    let data = data.clone(); //The drawing outlives this function
+    render_blocking(move || draw_card(&data, avatar, flag, top_play, accent, locale)).await
+}
+
+//Function to send the profile card of a player
+pub async fn send_card(ctx: &Context, msg: &Message, player_id: &str) {
+    let locale = locale::for_message(msg); //Language and number format
+    let data = match fetch_player_data(player_id).await { //Fetch data from ScoreSaber API
+        Ok(data) => data,
+        Err(e) => {
+            println!("Error fetching data from API: {}", e); //Print error
+            send_simple_format(ctx, msg, format!("{} (`!card` `player_id`)", locale.text("error.invalid_id")).as_str()).await; //Send error
+            return;
+        }
+    };
+    let accent = theme_pixel(theme::for_message(msg).accent); //Accent of the card follows the theme
+    match render_card(&data, accent, locale).await {
+        Ok(png) => {
+            let message = CreateMessage::new().add_file(CreateAttachment::bytes(png, format!("{}.png", data.id)));
+            if let Err(why) = msg.channel_id.send_message(&ctx.http, message).await { // If sending message has error
+                println!("Error sending message: {:?}", why); // Print error
+            } else {
+                println!("Profile card of `{}` sent to Discord (!card)", data.name); //Print success
+            }
+        }
+        Err(e) => {
+            println!("Error rendering card: {}", e); //Print error
+            send_simple_format(ctx, msg, locale.text("card.failed")).await; //Send error
+        }
+    }
+}
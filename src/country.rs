@@ -0,0 +1,118 @@
+use crate::PlayerData; //PlayerData struct
+use crate::imports::*; //Imports
+use crate::datatweaks::{create_db, fetch_players_page, fetch_linked_discord, fetch_players_by_country};
+use crate::format::{flag_emoji, profile_url};
+use crate::message::send_simple_format; //Function to send a simple embedded message
+use crate::locale::{self, Locale};
+use crate::theme; //Embed themes
+
+const MAX_TRACKED: usize = 10; //Amount of tracked players shown in the country stats
+
+//Function to check a country code (two letters, caps don't matter)
+fn parse_country(country: &str) -> Option<String> {
+    if country.len() == 2 && country.chars().all(|c| c.is_ascii_alphabetic()) {
+        Some(country.to_ascii_uppercase())
+    } else {
+        None
+    }
+}
+
+//Function to send a page of the top players of a country from ScoreSaber
+pub async fn send_country_leaderboard(ctx: &Context, msg: &Message, country: &str, page: &str) {
+    let locale = locale::for_message(msg); //Language and number format
+    let (country, page) = match (parse_country(country), if page.is_empty() { Ok(1) } else { page.parse::<i64>() }) { //Parse the parameters
+        (Some(country), Ok(page)) if page > 0 => (country, page),
+        _ => {
+            send_simple_format(ctx, msg, format!("{} (`!country` `CC` `page [OPTIONAL]`)", locale.text("country.invalid")).as_str()).await; //Send error
+            return;
+        }
+    };
+    let data = match fetch_players_page(page, Some(&country)).await { //Fetch the page from ScoreSaber API
+        Ok(data) => data,
+        Err(e) => {
+            println!("Error fetching country leaderboard from API: {}", e); //Print error
+            send_simple_format(ctx, msg, locale.text("country.fetch_failed")).await; //Send error
+            return;
+        }
+    };
+    if data.players.is_empty() { //Unknown country or page past the end
+        send_simple_format(ctx, msg, locale.fill("country.empty", &[&country, &page.to_string()]).as_str()).await; //Send error
+        return;
+    }
+
+    //Making the description (one line per player)
+    let mut description = String::new();
+    for player in &data.players {
+        description += &format!("**#{}** [{}]({}) - **{}pp** (#{})\n", locale.integer(player.countryRank, false), player.name, profile_url(&player.id), locale.decimal(player.pp, 2), locale.integer(player.rank, false));
+    }
+    let pages = if data.metadata.itemsPerPage > 0 { (data.metadata.total + data.metadata.itemsPerPage - 1) / data.metadata.itemsPerPage } else { page }; //Round up
+    let embed = CreateEmbed::new() //Make Embed
+        .color(theme::for_message(msg).color) //Set color to the theme
+        .title(locale.fill("country.title", &[&flag_emoji(&country), &country])) //Set title
+        .description(description) //Set description to the leaderboard
+        .footer(CreateEmbedFooter::new(locale.fill("country.footer", &[&page.to_string(), &locale.integer(pages, false), &locale.integer(data.metadata.total, false)])));
+    if let Err(why) = msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await { // If sending message has error
+        println!("Error sending message: {:?}", why); // Print error
+    }
+    println!("Country leaderboard sent to Discord (!country {} {})", country, page); //Print success
+}
+
+//Function to summarize the stored players of a country
+fn country_stats_embed(country: &str, players: &mut [PlayerData], linked: usize, locale: Locale) -> CreateEmbed {
+    let ranked: Vec<&PlayerData> = players.iter().filter(|player| player.pp > 0.0 && player.countryRank > 0).collect(); //Inactive players have no rank
+    let average = |value: fn(&PlayerData) -> f64| if ranked.is_empty() { 0.0 } else { ranked.iter().map(|player| value(player)).sum::<f64>() / ranked.len() as f64 };
+    let total_plays: i64 = players.iter().map(|player| player.scoreStats.rankedPlayCount).sum();
+
+    let mut embed = CreateEmbed::new() //Make Embed
+        .title(locale.fill("country_stats.title", &[&flag_emoji(country), country])) //Set title
+        .field(locale.text("country_stats.tracked"), locale.integer(players.len() as i64, false), true)
+        .field(locale.text("country_stats.linked"), locale.integer(linked as i64, false), true)
+        .field(locale.text("country_stats.ranked"), locale.integer(ranked.len() as i64, false), true)
+        .field(locale.text("country_stats.average_pp"), format!("{}pp", locale.decimal(average(|player| player.pp), 2)), true)
+        .field(locale.text("country_stats.average_acc"), format!("{}{}", locale.decimal(average(|player| player.scoreStats.averageRankedAccuracy), 2), locale.unit("%")), true)
+        .field(locale.text("metric.rankedPlays"), locale.integer(total_plays, false), true);
+
+    //Best tracked players by country rank (unranked last)
+    players.sort_by_key(|player| if player.countryRank > 0 { player.countryRank } else { i64::MAX });
+    let top: Vec<String> = players.iter().take(MAX_TRACKED).map(|player| {
+        format!("**#{}** {} - {}pp", locale.integer(player.countryRank, false), player.name, locale.decimal(player.pp, 2))
+    }).collect();
+    embed = embed.field(locale.text("country_stats.best"), top.join("\n"), false);
+    embed
+}
+
+//Function to send statistics of the players of a country stored in the database
+pub async fn send_country_stats(ctx: &Context, msg: &Message, country: &str) {
+    let locale = locale::for_message(msg); //Language and number format
+    let country = match parse_country(country) {
+        Some(country) => country,
+        None => {
+            send_simple_format(ctx, msg, format!("{} (`!country-stats` `CC`)", locale.text("country_stats.invalid")).as_str()).await; //Send error
+            return;
+        }
+    };
+    let players = {
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        create_db(&conn).and_then(|_| fetch_players_by_country(&conn, &country)).map(|players| {
+            let linked = players.iter().filter(|player| fetch_linked_discord(&conn, &player.id).ok().flatten().is_some()).count(); //Players linked to a discord
+            (players, linked)
+        })
+    };
+    let (mut players, linked) = match players {
+        Ok((players, linked)) if !players.is_empty() => (players, linked),
+        Ok(_) => {
+            send_simple_format(ctx, msg, format!("{} (`!stats` `player_id`)", locale.fill("country_stats.none", &[&country])).as_str()).await; //Send error
+            return;
+        }
+        Err(e) => {
+            println!("Error fetching country stats from database: {}", e); //Print error
+            send_simple_format(ctx, msg, locale.text("country_stats.failed")).await; //Send error
+            return;
+        }
+    };
+    let embed = country_stats_embed(&country, &mut players, linked, locale).color(theme::for_message(msg).color); //Set color to the theme
+    if let Err(why) = msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await { // If sending message has error
+        println!("Error sending message: {:?}", why); // Print error
+    }
+    println!("Country stats sent to Discord (!country-stats {})", country); //Print success
+}
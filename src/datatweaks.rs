@@ -1,8 +1,23 @@
 use crate::PlayerData; //PlayerData struct
 use crate::ScoreStats; //ScoreStats struct
 use crate::PlayersData; //PlayersData struct
+use crate::PlayerScore; //PlayerScore struct
+use crate::LeaderboardInfoCollection; //LeaderboardInfoCollection struct
+use crate::RankedMap; //RankedMap struct
+use crate::CachedScore; //CachedScore struct
+use crate::PlayerScoreCollection; //PlayerScoreCollection struct
+use crate::Snapshot; //Snapshot struct
+use crate::Goal; //Goal struct
+use crate::Reminder; //Reminder struct
+use crate::ProfileEvent; //ProfileEvent enum
+use crate::compare::compare_profile; //Function to find the profile events
+use crate::theme::ThemeSettings; //ThemeSettings struct
 use crate::imports::*; //Imports
 
+const PLAYERS_PER_PAGE: i64 = 50; //Players in each page of the ScoreSaber ranking
+pub const SCORES_PER_PAGE: i64 = 100; //Most scores ScoreSaber returns in one page
+const MAX_CACHE_PAGES: i64 = 100; //Most pages of scores cached for one player (10,000 scores)
+
 //Function used to fetch/take in the data from Scoresaber
 pub async fn fetch_player_data(player_id: &str) -> Result<PlayerData, Error> { //Name of function and stating return type
     let url = format!("https://scoresaber.com/api/player/{}/full", player_id); //The url used to take in data
@@ -17,6 +32,115 @@ pub async fn search_player(player_name: &str) -> Result<PlayersData, Error> {
     Ok(response)
 }
 
+// Function to fetch a page of the global ranking, or of a country if one is given (two letter code)
+pub async fn fetch_players_page(page: i64, country: Option<&str>) -> Result<PlayersData, Error> {
+    let url = match country {
+        Some(country) => format!("https://scoresaber.com/api/players?page={}&countries={}", page, country), // Construct the URL with the page and country
+        None => format!("https://scoresaber.com/api/players?page={}", page), // Construct the URL with the page
+    };
+    let response = reqwest::get(url).await?.json::<PlayersData>().await?; // Send the request and get the response
+    Ok(response)
+}
+
+// Function to fetch the players holding a range of ranks (country ranks if a country is given), fetching every page it covers
+pub async fn fetch_rank_range(first: i64, last: i64, country: Option<&str>) -> Result<Vec<PlayerData>, Error> {
+    let first = first.max(1);
+    let mut players = Vec::new();
+    let mut page = (first - 1) / PLAYERS_PER_PAGE + 1; //Page holding the first rank
+    while page <= (last - 1) / PLAYERS_PER_PAGE + 1 { //Until the page holding the last rank
+        let data = fetch_players_page(page, country).await?;
+        if data.players.is_empty() { //Past the end of the ranking
+            break;
+        }
+        players.extend(data.players.into_iter().filter(|player| {
+            let rank = if country.is_some() { player.countryRank } else { player.rank };
+            rank >= first && rank <= last
+        }));
+        page += 1;
+    }
+    Ok(players)
+}
+
+// Function to fetch a page of a player's scores (sort is "top" or "recent")
+pub async fn fetch_player_scores(player_id: &str, sort: &str, page: i64, limit: i64) -> Result<PlayerScoreCollection, Error> {
+    let url = format!("https://scoresaber.com/api/player/{}/scores?sort={}&page={}&limit={}", player_id, sort, page, limit); // Construct the URL with the sort and page
+    let response = reqwest::get(url).await?.json::<PlayerScoreCollection>().await?; // Send the request and get the response
+    Ok(response)
+}
+
+// Function to fetch a page of the ranked maps, newest ranked first
+pub async fn fetch_ranked_maps_page(page: i64) -> Result<LeaderboardInfoCollection, Error> {
+    let url = format!("https://scoresaber.com/api/leaderboards?ranked=true&category=1&sort=0&page={}", page); // Construct the URL with the page (category 1 is ranked date)
+    let response = reqwest::get(url).await?.json::<LeaderboardInfoCollection>().await?; // Send the request and get the response
+    Ok(response)
+}
+
+// Function to fetch several pages of a player's scores (stops early at the last page)
+pub async fn fetch_all_scores(player_id: &str, sort: &str, max_pages: i64) -> Result<Vec<PlayerScore>, Error> {
+    let mut scores = Vec::new();
+    for page in 1..=max_pages {
+        let collection = fetch_player_scores(player_id, sort, page, SCORES_PER_PAGE).await?;
+        let last_page = (collection.playerScores.len() as i64) < SCORES_PER_PAGE;
+        scores.extend(collection.playerScores);
+        if last_page { //No more scores
+            break;
+        }
+    }
+    Ok(scores)
+}
+
+// Function to sync the score cache of a player (newest first, stops at the first cached score once a full sync has finished)
+pub async fn sync_score_cache(player_id: &str) -> bool {
+    let synced_before = {
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        match create_db(&conn).and_then(|_| fetch_score_sync(&conn, player_id)) {
+            Ok(time) => time.is_some(),
+            Err(e) => {
+                println!("Error fetching score cache state from database: {}", e); //Print error
+                return false;
+            }
+        }
+    };
+    for page in 1..=MAX_CACHE_PAGES { //Loop through the pages, newest first
+        let collection = match fetch_player_scores(player_id, "recent", page, SCORES_PER_PAGE).await { //Fetch the page from ScoreSaber API
+            Ok(collection) => collection,
+            Err(e) => {
+                println!("Error fetching scores of `{}` from API: {}", player_id, e); //Print error
+                return false;
+            }
+        };
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        let mut reached_cached = false;
+        for score in &collection.playerScores { //Store every score of the page
+            match is_score_cached(&conn, player_id, score.score.id).and_then(|cached| {
+                reached_cached |= cached;
+                save_cached_score(&conn, player_id, score)
+            }) {
+                Ok(()) => {}
+                Err(e) => {
+                    println!("Error caching scores: {}", e); //Print error
+                    return false;
+                }
+            }
+        }
+        if (collection.playerScores.len() as i64) < SCORES_PER_PAGE || (synced_before && reached_cached) { //Last page or everything older is cached
+            break;
+        }
+    }
+    let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+    if let Err(e) = set_score_sync(&conn, player_id, current_time()) { //Remember the cache is complete
+        println!("Error updating score cache state: {}", e); //Print error
+        return false;
+    }
+    true
+}
+
+// Function to download an image (avatars, flags and covers)
+pub async fn fetch_bytes(url: &str) -> Result<Vec<u8>, Error> {
+    let response = reqwest::get(url).await?.error_for_status()?.bytes().await?; // Send the request and get the bytes
+    Ok(response.to_vec())
+}
+
 //Function to create database
 pub fn create_db(conn: &Connection) -> Result<()> {
     conn.execute(
@@ -42,16 +166,210 @@ pub fn create_db(conn: &Connection) -> Result<()> {
         )",
         params![],
     )?;
+    conn.execute( //History of player stats, one row every time the player is fetched
+        "CREATE TABLE IF NOT EXISTS snapshots (
+            id TEXT,
+            timestamp INTEGER,
+            pp REAL,
+            rank INTEGER,
+            countryRank INTEGER,
+            averageRankedAccuracy REAL,
+            totalScore INTEGER,
+            totalRankedScore INTEGER,
+            totalPlayCount INTEGER,
+            rankedPlayCount INTEGER,
+            replaysWatched INTEGER
+        )",
+        params![],
+    )?;
+    conn.execute( //Settings of each guild
+        "CREATE TABLE IF NOT EXISTS guild_settings (
+            guild TEXT PRIMARY KEY,
+            channel TEXT,
+            weeklyPosted INTEGER
+        )",
+        params![],
+    )?;
+    conn.execute( //Rivals declared by linked players
+        "CREATE TABLE IF NOT EXISTS rivals (
+            player TEXT,
+            rival TEXT,
+            guild TEXT,
+            PRIMARY KEY (player, rival)
+        )",
+        params![],
+    )?;
+    conn.execute( //Milestones each player has reached (so they are only announced once)
+        "CREATE TABLE IF NOT EXISTS milestones (
+            player TEXT,
+            metric TEXT,
+            value REAL,
+            timestamp INTEGER,
+            PRIMARY KEY (player, metric, value)
+        )",
+        params![],
+    )?;
+    conn.execute( //Extra milestones configured by each guild
+        "CREATE TABLE IF NOT EXISTS milestone_thresholds (
+            guild TEXT,
+            metric TEXT,
+            value REAL,
+            PRIMARY KEY (guild, metric, value)
+        )",
+        params![],
+    )?;
+    conn.execute( //Goals set by each discord
+        "CREATE TABLE IF NOT EXISTS goals (
+            discord TEXT,
+            metric TEXT,
+            target REAL,
+            start REAL,
+            created INTEGER,
+            deadline INTEGER,
+            reached INTEGER,
+            PRIMARY KEY (discord, metric)
+        )",
+        params![],
+    )?;
+    conn.execute( //Every profile event of a player (renames, bans, inactivity, country and avatar changes)
+        "CREATE TABLE IF NOT EXISTS profile_history (
+            id TEXT,
+            event TEXT,
+            old TEXT,
+            new TEXT,
+            timestamp INTEGER
+        )",
+        params![],
+    )?;
+    conn.execute( //Embed themes picked by users and guilds
+        "CREATE TABLE IF NOT EXISTS themes (
+            scope TEXT,
+            owner TEXT,
+            color INTEGER,
+            accent INTEGER,
+            layout TEXT,
+            PRIMARY KEY (scope, owner)
+        )",
+        params![],
+    )?;
+    conn.execute( //Languages picked by users and guilds
+        "CREATE TABLE IF NOT EXISTS locales (
+            scope TEXT,
+            owner TEXT,
+            locale TEXT,
+            PRIMARY KEY (scope, owner)
+        )",
+        params![],
+    )?;
+    conn.execute( //Ranked map catalogue synced from ScoreSaber (one row per difficulty)
+        "CREATE TABLE IF NOT EXISTS maps (
+            leaderboardId INTEGER PRIMARY KEY,
+            songHash TEXT,
+            songName TEXT,
+            songSubName TEXT,
+            songAuthorName TEXT,
+            levelAuthorName TEXT,
+            difficulty INTEGER,
+            gameMode TEXT,
+            stars REAL,
+            maxScore INTEGER,
+            rankedDate INTEGER,
+            coverImage TEXT,
+            ranked INTEGER,
+            lastSeen INTEGER
+        )",
+        params![],
+    )?;
+    conn.execute( //Last time each kind of map sync finished
+        "CREATE TABLE IF NOT EXISTS map_sync (
+            kind TEXT PRIMARY KEY,
+            timestamp INTEGER
+        )",
+        params![],
+    )?;
+    conn.execute( //Cached scores of players (one row per player and map, replaced when improved)
+        "CREATE TABLE IF NOT EXISTS scores (
+            player TEXT,
+            leaderboardId INTEGER,
+            scoreId INTEGER,
+            baseScore INTEGER,
+            modifiedScore INTEGER,
+            modifiers TEXT,
+            pp REAL,
+            timeSet TEXT,
+            maxScore INTEGER,
+            stars REAL,
+            songName TEXT,
+            levelAuthorName TEXT,
+            difficulty INTEGER,
+            PRIMARY KEY (player, leaderboardId)
+        )",
+        params![],
+    )?;
+    conn.execute( //Last time the score cache of each player was fully synced
+        "CREATE TABLE IF NOT EXISTS score_sync (
+            player TEXT PRIMARY KEY,
+            timestamp INTEGER
+        )",
+        params![],
+    )?;
+    conn.execute( //Inactivity reminders discords opted into
+        "CREATE TABLE IF NOT EXISTS reminders (
+            discord TEXT PRIMARY KEY,
+            user TEXT,
+            channel TEXT,
+            days INTEGER,
+            reminded INTEGER
+        )",
+        params![],
+    )?;
+    conn.execute( //Stats each discord picked for the stats embed
+        "CREATE TABLE IF NOT EXISTS field_selections (
+            discord TEXT PRIMARY KEY,
+            metrics TEXT
+        )",
+        params![],
+    )?;
+    Ok(())
+}
+
+//Function to get the current time (seconds since unix epoch)
+pub fn current_time() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|time| time.as_secs() as i64)
+        .unwrap_or(0) //If the clock is before 1970 then use 0
+}
+
+//Function to store a profile event in the profile history (old and new are the data before and after the event)
+fn save_profile_event(conn: &Connection, event: &ProfileEvent, old: &PlayerData, new: &PlayerData) -> Result<()> {
+    let (kind, before, after) = match event {
+        ProfileEvent::Inactive(inactive) => ("inactive", (!inactive).to_string(), inactive.to_string()),
+        ProfileEvent::Banned(banned) => ("banned", (!banned).to_string(), banned.to_string()),
+        ProfileEvent::Renamed { old, new } => ("renamed", old.clone(), new.clone()),
+        ProfileEvent::CountryChanged { old, new } => ("country", old.clone(), new.clone()),
+        ProfileEvent::AvatarChanged => ("avatar", old.profilePicture.clone(), new.profilePicture.clone()),
+    };
+    conn.execute(
+        "INSERT INTO profile_history(id, event, old, new, timestamp) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![new.id, kind, before, after, current_time()],
+    )?;
     Ok(())
 }
 
-// Function to insert data into database
-pub fn insert_player_data(conn: &Connection, data: &PlayerData, new_user: bool) -> Result<()> {
+// Function to insert data into database (returns the profile events since the stored data, they are kept in the profile history)
+pub fn insert_player_data(conn: &Connection, data: &PlayerData, new_user: bool) -> Result<Vec<ProfileEvent>> {
 
     let mut stmt = conn.prepare("SELECT discord FROM player_data WHERE id = ?1")?;
     let mut discord_id = String::from("NULL");
+    let mut events = Vec::new();
     if !new_user { //If not new user
         discord_id = stmt.query_row(params![data.id], |row| row.get(0))?;
+        let data_from_db = fetch_player_data_from_db(conn, &data.id)?;
+        events = compare_profile(data, &data_from_db);
+        for event in &events { //Keep every event in the history
+            save_profile_event(conn, event, &data_from_db, data)?;
+        }
     }
 
     conn.execute(
@@ -81,9 +399,151 @@ pub fn insert_player_data(conn: &Connection, data: &PlayerData, new_user: bool)
             discord_id
         ],
     )?;
+    insert_snapshot(conn, data)?; //Keep a history of the stats
+    Ok(events)
+}
+
+//Function to insert a snapshot of the stats into the history
+pub fn insert_snapshot(conn: &Connection, data: &PlayerData) -> Result<()> {
+    conn.execute(
+        "INSERT INTO snapshots(
+            id, timestamp, pp, rank, countryRank, averageRankedAccuracy, totalScore,
+            totalRankedScore, totalPlayCount, rankedPlayCount, replaysWatched
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+        params![
+            data.id,
+            current_time(),
+            data.pp,
+            data.rank,
+            data.countryRank,
+            data.scoreStats.averageRankedAccuracy,
+            data.scoreStats.totalScore,
+            data.scoreStats.totalRankedScore,
+            data.scoreStats.totalPlayCount,
+            data.scoreStats.rankedPlayCount,
+            data.scoreStats.replaysWatched
+        ],
+    )?;
     Ok(())
 }
 
+//Columns of a snapshot in the order they are mapped
+const SNAPSHOT_COLUMNS: &str = "timestamp, pp, rank, countryRank, averageRankedAccuracy, totalScore, totalRankedScore,
+    totalPlayCount, rankedPlayCount, replaysWatched";
+
+//Function to map a row (selected with SNAPSHOT_COLUMNS) to the Snapshot struct
+fn snapshot_from_row(row: &rusqlite::Row) -> Result<Snapshot> {
+    Ok(Snapshot {
+        timestamp: row.get(0)?,
+        pp: row.get(1)?,
+        rank: row.get(2)?,
+        countryRank: row.get(3)?,
+        averageRankedAccuracy: row.get(4)?,
+        totalScore: row.get(5)?,
+        totalRankedScore: row.get(6)?,
+        totalPlayCount: row.get(7)?,
+        rankedPlayCount: row.get(8)?,
+        replaysWatched: row.get(9)?,
+    })
+}
+
+//Fetch the oldest snapshot of a player taken at or after the timestamp
+pub fn fetch_snapshot_since(conn: &Connection, player_id: &str, timestamp: i64) -> Result<Option<Snapshot>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM snapshots WHERE id = ?1 AND timestamp >= ?2 ORDER BY timestamp ASC LIMIT 1", SNAPSHOT_COLUMNS
+    ))?; // Prepare the query
+    let mut rows = stmt.query(params![player_id, timestamp])?; // Execute the query
+
+    if let Some(row) = rows.next()? { // If a snapshot was found
+        Ok(Some(snapshot_from_row(row)?))
+    } else { // If the player has no snapshots in that time
+        Ok(None)
+    }
+}
+
+//Fetch the newest snapshot of a player taken at or before the timestamp
+pub fn fetch_snapshot_before(conn: &Connection, player_id: &str, timestamp: i64) -> Result<Option<Snapshot>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM snapshots WHERE id = ?1 AND timestamp <= ?2 ORDER BY timestamp DESC LIMIT 1", SNAPSHOT_COLUMNS
+    ))?; // Prepare the query
+    let mut rows = stmt.query(params![player_id, timestamp])?; // Execute the query
+
+    if let Some(row) = rows.next()? { // If a snapshot was found
+        Ok(Some(snapshot_from_row(row)?))
+    } else { // If the player has no snapshots in that time
+        Ok(None)
+    }
+}
+
+//Fetch every snapshot of a player taken at or after the timestamp (oldest first)
+pub fn fetch_snapshots_since(conn: &Connection, player_id: &str, timestamp: i64) -> Result<Vec<Snapshot>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM snapshots WHERE id = ?1 AND timestamp >= ?2 ORDER BY timestamp ASC", SNAPSHOT_COLUMNS
+    ))?; // Prepare the query
+    let snapshots = stmt.query_map(params![player_id, timestamp], snapshot_from_row)?; // Execute the query
+    snapshots.collect()
+}
+
+//Fetch the time of the latest snapshot (when the player was last updated)
+pub fn last_updated(conn: &Connection, player_id: &str) -> Result<Option<i64>> {
+    conn.query_row(
+        "SELECT MAX(timestamp) FROM snapshots WHERE id = ?1",
+        params![player_id],
+        |row| row.get(0),
+    )
+}
+
+//Function to fetch the discords of every member of a guild (from the member list of the guild)
+pub async fn fetch_guild_discords(ctx: &Context, guild_id: GuildId) -> Result<HashSet<String>, SerenityError> {
+    let mut discords = HashSet::new();
+    let mut after = None;
+    loop { //Members come in pages of up to 1000
+        let members = guild_id.members(&ctx.http, Some(1000), after).await?;
+        discords.extend(members.iter().map(|member| member.user.name.clone()));
+        match members.last() {
+            Some(last) if members.len() == 1000 => after = Some(last.user.id), //Next page starts after the last member
+            _ => break,
+        }
+    }
+    Ok(discords)
+}
+
+//Fetch the ids (and linked discords) of the players linked to one of the discords
+pub fn fetch_guild_player_ids(conn: &Connection, discords: &HashSet<String>) -> Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare("SELECT id, discord FROM player_data WHERE discord IS NOT NULL")?;
+    let ids = stmt.query_map(params![], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+    let mut linked = Vec::new();
+    for id in ids {
+        let (player_id, discord) = id?;
+        if discords.contains(&discord) { //Only the discords that are in the guild
+            linked.push((player_id, discord));
+        }
+    }
+    Ok(linked)
+}
+
+//Function to refetch and store the data of players (used for stale rows)
+pub async fn refresh_players(player_ids: Vec<String>) {
+    for player_id in player_ids { //Loop through every player
+        match fetch_player_data(&player_id).await { //Fetch data from ScoreSaber API
+            Ok(data) => { //If functioned correctly
+                let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+                if let Err(e) = insert_player_data(&conn, &data, false) { //Store the new data
+                    println!("Error inserting refreshed data into database: {}", e); //Print error
+                }
+            }
+            Err(e) => println!("Error refreshing player `{}` from API: {}", player_id, e), //Print error
+        }
+    }
+}
+
+//Fetch every stored player of a country
+pub fn fetch_players_by_country(conn: &Connection, country: &str) -> Result<Vec<PlayerData>> {
+    let mut stmt = conn.prepare("SELECT id FROM player_data WHERE country = ?1")?;
+    let ids = stmt.query_map(params![country], |row| row.get::<_, String>(0))?.collect::<Result<Vec<String>>>()?;
+    ids.iter().map(|player_id| fetch_player_data_from_db(conn, player_id)).collect()
+}
+
 //Fetch player data from database
 pub fn fetch_player_data_from_db(conn: &Connection, player_id: &str) -> Result<PlayerData, rusqlite::Error> {
     let mut stmt = conn.prepare("SELECT *, name FROM player_data WHERE id = ?1")?; // Prepare the query
@@ -156,6 +616,16 @@ pub async fn discord_linked(discord_id: &str) -> Result<Option<String>> {
     }
 }
 
+// Function to find the discord linked to a player (None if not linked)
+pub fn fetch_linked_discord(conn: &Connection, player_id: &str) -> Result<Option<String>> {
+    let discord: Result<Option<String>> = conn.query_row("SELECT discord FROM player_data WHERE id = ?1", params![player_id], |row| row.get(0));
+    match discord {
+        Ok(Some(discord)) if discord != "NULL" => Ok(Some(discord)), // New players are stored with "NULL"
+        Ok(_) | Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None), // If not linked return None
+        Err(e) => Err(e),
+    }
+}
+
 //Function to delete old discord link
 pub async fn delete_discord_if_exists(discord_id: &str) -> Result<bool> {
     let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
@@ -168,5 +638,430 @@ pub async fn delete_discord_if_exists(discord_id: &str) -> Result<bool> {
     } else { //If discord not found
         return Ok(false); //Return false to indicate failure
     }
-    return Ok(true); // Return true to indicate success
-}
\ No newline at end of file
+    Ok(true) // Return true to indicate success
+}
+
+//Function to set the channel a guild's announcements are posted in
+pub fn set_announce_channel(conn: &Connection, guild_id: &str, channel_id: &str, weekly_posted: i64) -> Result<()> {
+    conn.execute(
+        "INSERT INTO guild_settings(guild, channel, weeklyPosted) VALUES (?1, ?2, ?3)
+        ON CONFLICT(guild) DO UPDATE SET channel = excluded.channel",
+        params![guild_id, channel_id, weekly_posted],
+    )?;
+    Ok(())
+}
+
+//Fetch the announcement channel of a guild
+pub fn fetch_announce_channel(conn: &Connection, guild_id: &str) -> Result<Option<String>> {
+    let channel: Result<Option<String>> = conn.query_row(
+        "SELECT channel FROM guild_settings WHERE guild = ?1",
+        params![guild_id],
+        |row| row.get(0),
+    );
+    match channel {
+        Ok(channel) => Ok(channel), // If exists return channel
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None), // If guild has no settings return None
+        Err(e) => Err(e),
+    }
+}
+
+//Fetch every guild with an announcement channel (guild, channel, week the climbers were last posted)
+pub fn fetch_announce_channels(conn: &Connection) -> Result<Vec<(String, String, i64)>> {
+    let mut stmt = conn.prepare("SELECT guild, channel, weeklyPosted FROM guild_settings WHERE channel IS NOT NULL")?;
+    let channels = stmt.query_map(params![], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+    channels.collect()
+}
+
+//Function to remember the week the climbers were posted in a guild
+pub fn set_weekly_posted(conn: &Connection, guild_id: &str, week: i64) -> Result<()> {
+    conn.execute(
+        "UPDATE guild_settings SET weeklyPosted = ?1 WHERE guild = ?2",
+        params![week, guild_id],
+    )?;
+    Ok(())
+}
+
+//Function to add a rival to a player
+pub fn add_rival(conn: &Connection, player_id: &str, rival_id: &str, guild_id: &str) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO rivals(player, rival, guild) VALUES (?1, ?2, ?3)",
+        params![player_id, rival_id, guild_id],
+    )?;
+    Ok(())
+}
+
+//Function to remove a rival from a player (returns false if they were not rivals)
+pub fn remove_rival(conn: &Connection, player_id: &str, rival_id: &str) -> Result<bool> {
+    let removed = conn.execute(
+        "DELETE FROM rivals WHERE player = ?1 AND rival = ?2",
+        params![player_id, rival_id],
+    )?;
+    Ok(removed > 0)
+}
+
+//Fetch every rivalry a player is part of, in either direction (other player, guild)
+pub fn fetch_rivalries(conn: &Connection, player_id: &str) -> Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare(
+        "SELECT rival, guild FROM rivals WHERE player = ?1
+        UNION SELECT player, guild FROM rivals WHERE rival = ?1",
+    )?;
+    let rivalries = stmt.query_map(params![player_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    rivalries.collect()
+}
+
+//Fetch the rivals a player has declared
+pub fn fetch_rivals(conn: &Connection, player_id: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT rival FROM rivals WHERE player = ?1")?;
+    let rivals = stmt.query_map(params![player_id], |row| row.get(0))?;
+    rivals.collect()
+}
+
+//Function to record a milestone as reached (returns false if it was already reached before)
+pub fn record_milestone(conn: &Connection, player_id: &str, metric: &str, value: f64) -> Result<bool> {
+    let inserted = conn.execute(
+        "INSERT OR IGNORE INTO milestones(player, metric, value, timestamp) VALUES (?1, ?2, ?3, ?4)",
+        params![player_id, metric, value, current_time()],
+    )?;
+    Ok(inserted > 0)
+}
+
+//Function to add a milestone to a guild
+pub fn add_milestone_threshold(conn: &Connection, guild_id: &str, metric: &str, value: f64) -> Result<()> {
+    conn.execute(
+        "INSERT OR IGNORE INTO milestone_thresholds(guild, metric, value) VALUES (?1, ?2, ?3)",
+        params![guild_id, metric, value],
+    )?;
+    Ok(())
+}
+
+//Function to remove a milestone from a guild (returns false if it did not exist)
+pub fn remove_milestone_threshold(conn: &Connection, guild_id: &str, metric: &str, value: f64) -> Result<bool> {
+    let removed = conn.execute(
+        "DELETE FROM milestone_thresholds WHERE guild = ?1 AND metric = ?2 AND value = ?3",
+        params![guild_id, metric, value],
+    )?;
+    Ok(removed > 0)
+}
+
+//Fetch the milestones configured by a guild (metric, value)
+pub fn fetch_milestone_thresholds(conn: &Connection, guild_id: &str) -> Result<Vec<(String, f64)>> {
+    let mut stmt = conn.prepare("SELECT metric, value FROM milestone_thresholds WHERE guild = ?1 ORDER BY metric, value")?;
+    let thresholds = stmt.query_map(params![guild_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    thresholds.collect()
+}
+
+//Function to set a goal (replaces the old goal of the same metric)
+pub fn set_goal(conn: &Connection, discord_id: &str, goal: &Goal) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO goals(discord, metric, target, start, created, deadline, reached)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![discord_id, goal.metric, goal.target, goal.start, goal.created, goal.deadline, goal.reached],
+    )?;
+    Ok(())
+}
+
+//Function to remove a goal (returns false if there was no goal)
+pub fn remove_goal(conn: &Connection, discord_id: &str, metric: &str) -> Result<bool> {
+    let removed = conn.execute("DELETE FROM goals WHERE discord = ?1 AND metric = ?2", params![discord_id, metric])?;
+    Ok(removed > 0)
+}
+
+//Fetch the goals of a discord
+pub fn fetch_goals(conn: &Connection, discord_id: &str) -> Result<Vec<Goal>> {
+    let mut stmt = conn.prepare("SELECT metric, target, start, created, deadline, reached FROM goals WHERE discord = ?1 ORDER BY metric")?;
+    let goals = stmt.query_map(params![discord_id], |row| {
+        Ok(Goal {
+            metric: row.get(0)?,
+            target: row.get(1)?,
+            start: row.get(2)?,
+            created: row.get(3)?,
+            deadline: row.get(4)?,
+            reached: row.get(5)?,
+        })
+    })?;
+    goals.collect()
+}
+
+//Function to mark a goal as reached
+pub fn set_goal_reached(conn: &Connection, discord_id: &str, metric: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE goals SET reached = ?1 WHERE discord = ?2 AND metric = ?3",
+        params![current_time(), discord_id, metric],
+    )?;
+    Ok(())
+}
+
+//Fetch the theme settings of a user or guild (scope is "user" or "guild")
+pub fn fetch_theme_settings(conn: &Connection, scope: &str, owner: &str) -> Result<Option<ThemeSettings>> {
+    let settings = conn.query_row(
+        "SELECT color, accent, layout FROM themes WHERE scope = ?1 AND owner = ?2",
+        params![scope, owner],
+        |row| Ok(ThemeSettings { color: row.get(0)?, accent: row.get(1)?, layout: row.get(2)? }),
+    );
+    match settings {
+        Ok(settings) => Ok(Some(settings)), // If exists return settings
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None), // If no theme return None
+        Err(e) => Err(e),
+    }
+}
+
+//Function to save the theme settings of a user or guild
+pub fn save_theme_settings(conn: &Connection, scope: &str, owner: &str, settings: &ThemeSettings) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO themes(scope, owner, color, accent, layout) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![scope, owner, settings.color, settings.accent, settings.layout],
+    )?;
+    Ok(())
+}
+
+//Function to delete the theme settings of a user or guild
+pub fn delete_theme_settings(conn: &Connection, scope: &str, owner: &str) -> Result<()> {
+    conn.execute("DELETE FROM themes WHERE scope = ?1 AND owner = ?2", params![scope, owner])?;
+    Ok(())
+}
+
+//Fetch the stats a discord picked (comma separated metric names)
+pub fn fetch_field_selection(conn: &Connection, discord_id: &str) -> Result<Option<String>> {
+    let selection = conn.query_row("SELECT metrics FROM field_selections WHERE discord = ?1", params![discord_id], |row| row.get(0));
+    match selection {
+        Ok(selection) => Ok(Some(selection)), // If exists return selection
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None), // If nothing picked return None
+        Err(e) => Err(e),
+    }
+}
+
+//Function to save the stats a discord picked
+pub fn save_field_selection(conn: &Connection, discord_id: &str, metrics: &str) -> Result<()> {
+    conn.execute("INSERT OR REPLACE INTO field_selections(discord, metrics) VALUES (?1, ?2)", params![discord_id, metrics])?;
+    Ok(())
+}
+
+//Function to delete the stats a discord picked
+pub fn delete_field_selection(conn: &Connection, discord_id: &str) -> Result<()> {
+    conn.execute("DELETE FROM field_selections WHERE discord = ?1", params![discord_id])?;
+    Ok(())
+}
+//Fetch the language code picked by a user or guild (scope is "user" or "guild")
+pub fn fetch_locale(conn: &Connection, scope: &str, owner: &str) -> Result<Option<String>> {
+    let locale = conn.query_row("SELECT locale FROM locales WHERE scope = ?1 AND owner = ?2", params![scope, owner], |row| row.get(0));
+    match locale {
+        Ok(locale) => Ok(Some(locale)), // If exists return language code
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None), // If nothing picked return None
+        Err(e) => Err(e),
+    }
+}
+
+//Function to save the language code picked by a user or guild
+pub fn save_locale(conn: &Connection, scope: &str, owner: &str, locale: &str) -> Result<()> {
+    conn.execute("INSERT OR REPLACE INTO locales(scope, owner, locale) VALUES (?1, ?2, ?3)", params![scope, owner, locale])?;
+    Ok(())
+}
+
+//Function to delete the language picked by a user or guild
+pub fn delete_locale(conn: &Connection, scope: &str, owner: &str) -> Result<()> {
+    conn.execute("DELETE FROM locales WHERE scope = ?1 AND owner = ?2", params![scope, owner])?;
+    Ok(())
+}
+
+//Columns selected for the RankedMap struct (in order)
+const MAP_COLUMNS: &str = "leaderboardId, songHash, songName, songSubName, songAuthorName, levelAuthorName, difficulty, gameMode, \
+    stars, maxScore, rankedDate, coverImage, ranked, lastSeen";
+
+//Function to map a row (selected with MAP_COLUMNS) to the RankedMap struct
+fn map_from_row(row: &rusqlite::Row) -> Result<RankedMap> {
+    Ok(RankedMap {
+        leaderboardId: row.get(0)?,
+        songHash: row.get(1)?,
+        songName: row.get(2)?,
+        songSubName: row.get(3)?,
+        songAuthorName: row.get(4)?,
+        levelAuthorName: row.get(5)?,
+        difficulty: row.get(6)?,
+        gameMode: row.get(7)?,
+        stars: row.get(8)?,
+        maxScore: row.get(9)?,
+        rankedDate: row.get(10)?,
+        coverImage: row.get(11)?,
+        ranked: row.get(12)?,
+        lastSeen: row.get(13)?,
+    })
+}
+
+//Fetch a map of the catalogue
+pub fn fetch_map(conn: &Connection, leaderboard_id: i64) -> Result<Option<RankedMap>> {
+    let map = conn.query_row(&format!("SELECT {} FROM maps WHERE leaderboardId = ?1", MAP_COLUMNS), params![leaderboard_id], map_from_row);
+    match map {
+        Ok(map) => Ok(Some(map)), // If exists return map
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None), // If not stored return None
+        Err(e) => Err(e),
+    }
+}
+
+//Function to insert or update a map of the catalogue
+pub fn save_map(conn: &Connection, map: &RankedMap) -> Result<()> {
+    conn.execute(
+        &format!("INSERT OR REPLACE INTO maps({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)", MAP_COLUMNS),
+        params![
+            map.leaderboardId, map.songHash, map.songName, map.songSubName, map.songAuthorName, map.levelAuthorName, map.difficulty,
+            map.gameMode, map.stars, map.maxScore, map.rankedDate, map.coverImage, map.ranked, map.lastSeen
+        ],
+    )?;
+    Ok(())
+}
+
+//Function to mark the ranked maps not seen since a time as unranked (returns the maps that were unranked)
+pub fn unrank_maps_not_seen_since(conn: &Connection, time: i64) -> Result<Vec<RankedMap>> {
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM maps WHERE ranked = 1 AND lastSeen < ?1", MAP_COLUMNS))?;
+    let maps = stmt.query_map(params![time], map_from_row)?.collect::<Result<Vec<RankedMap>>>()?;
+    conn.execute("UPDATE maps SET ranked = 0 WHERE ranked = 1 AND lastSeen < ?1", params![time])?;
+    Ok(maps)
+}
+
+//Fetch the ranked maps of the catalogue within a star range
+pub fn fetch_ranked_maps_in_range(conn: &Connection, min_stars: f64, max_stars: f64) -> Result<Vec<RankedMap>> {
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM maps WHERE ranked = 1 AND stars >= ?1 AND stars <= ?2", MAP_COLUMNS))?;
+    let maps = stmt.query_map(params![min_stars, max_stars], map_from_row)?.collect::<Result<Vec<RankedMap>>>()?;
+    Ok(maps)
+}
+
+//Function to search the catalogue by song name (hardest difficulty of the closest name first)
+pub fn search_maps(conn: &Connection, query: &str) -> Result<Vec<RankedMap>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM maps WHERE songName LIKE ?1 ORDER BY LOWER(songName) = LOWER(?2) DESC, LENGTH(songName), songHash, stars DESC", MAP_COLUMNS
+    ))?;
+    let maps = stmt.query_map(params![format!("%{}%", query), query], map_from_row)?.collect::<Result<Vec<RankedMap>>>()?;
+    Ok(maps)
+}
+
+//Fetch the last time a kind of map sync finished ("full" or "incremental")
+pub fn fetch_map_sync(conn: &Connection, kind: &str) -> Result<Option<i64>> {
+    let time = conn.query_row("SELECT timestamp FROM map_sync WHERE kind = ?1", params![kind], |row| row.get(0));
+    match time {
+        Ok(time) => Ok(Some(time)), // If exists return time
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None), // If never synced return None
+        Err(e) => Err(e),
+    }
+}
+
+//Function to remember when a kind of map sync finished
+pub fn set_map_sync(conn: &Connection, kind: &str, time: i64) -> Result<()> {
+    conn.execute("INSERT OR REPLACE INTO map_sync(kind, timestamp) VALUES (?1, ?2)", params![kind, time])?;
+    Ok(())
+}
+
+//Fetch the last time the score cache of a player was fully synced
+pub fn fetch_score_sync(conn: &Connection, player_id: &str) -> Result<Option<i64>> {
+    let time = conn.query_row("SELECT timestamp FROM score_sync WHERE player = ?1", params![player_id], |row| row.get(0));
+    match time {
+        Ok(time) => Ok(Some(time)), // If exists return time
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None), // If never synced return None
+        Err(e) => Err(e),
+    }
+}
+
+//Function to remember when the score cache of a player was synced
+pub fn set_score_sync(conn: &Connection, player_id: &str, time: i64) -> Result<()> {
+    conn.execute("INSERT OR REPLACE INTO score_sync(player, timestamp) VALUES (?1, ?2)", params![player_id, time])?;
+    Ok(())
+}
+
+//Fetch when every cached score of a player was set
+pub fn fetch_score_times(conn: &Connection, player_id: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT timeSet FROM scores WHERE player = ?1")?;
+    let times = stmt.query_map(params![player_id], |row| row.get(0))?;
+    times.collect()
+}
+
+//Function to check if a score is already in the cache
+pub fn is_score_cached(conn: &Connection, player_id: &str, score_id: i64) -> Result<bool> {
+    conn.query_row("SELECT COUNT(*) FROM scores WHERE player = ?1 AND scoreId = ?2", params![player_id, score_id], |row| row.get::<_, i64>(0)).map(|count| count > 0)
+}
+
+//Function to store a score in the cache (replaces the older score on the same map)
+pub fn save_cached_score(conn: &Connection, player_id: &str, score: &PlayerScore) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO scores(player, leaderboardId, scoreId, baseScore, modifiedScore, modifiers, pp, timeSet, maxScore, stars, songName, levelAuthorName, difficulty)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+        params![
+            player_id, score.leaderboard.id, score.score.id, score.score.baseScore, score.score.modifiedScore, score.score.modifiers, score.score.pp,
+            score.score.timeSet, score.leaderboard.maxScore,
+            score.leaderboard.stars, score.leaderboard.songName, score.leaderboard.levelAuthorName, score.leaderboard.difficulty.difficulty
+        ],
+    )?;
+    Ok(())
+}
+
+//Columns selected for the CachedScore struct (in order)
+const SCORE_COLUMNS: &str = "leaderboardId, baseScore, modifiedScore, modifiers, pp, maxScore, stars, songName, levelAuthorName, difficulty";
+
+//Function to map a row (selected with SCORE_COLUMNS) to the CachedScore struct
+fn cached_score_from_row(row: &rusqlite::Row) -> Result<CachedScore> {
+    Ok(CachedScore {
+        leaderboardId: row.get(0)?,
+        baseScore: row.get(1)?,
+        modifiedScore: row.get(2)?,
+        modifiers: row.get(3)?,
+        pp: row.get(4)?,
+        maxScore: row.get(5)?,
+        stars: row.get(6)?,
+        songName: row.get(7)?,
+        levelAuthorName: row.get(8)?,
+        difficulty: row.get(9)?,
+    })
+}
+
+//Fetch every cached score of a player
+pub fn fetch_cached_scores(conn: &Connection, player_id: &str) -> Result<Vec<CachedScore>> {
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM scores WHERE player = ?1", SCORE_COLUMNS))?;
+    let scores = stmt.query_map(params![player_id], cached_score_from_row)?.collect::<Result<Vec<CachedScore>>>()?;
+    Ok(scores)
+}
+
+//Fetch every cached score on a map (player and score)
+pub fn fetch_map_scores(conn: &Connection, leaderboard_id: i64) -> Result<Vec<(String, CachedScore)>> {
+    let mut stmt = conn.prepare(&format!("SELECT {}, player FROM scores WHERE leaderboardId = ?1", SCORE_COLUMNS))?;
+    let scores = stmt.query_map(params![leaderboard_id], |row| {
+        Ok((row.get(10)?, cached_score_from_row(row)?))
+    })?.collect::<Result<Vec<(String, CachedScore)>>>()?;
+    Ok(scores)
+}
+
+//Function to opt a discord into inactivity reminders (replaces the old settings)
+pub fn set_reminder(conn: &Connection, discord_id: &str, user_id: &str, channel_id: &str, days: i64) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO reminders(discord, user, channel, days, reminded) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![discord_id, user_id, channel_id, days, 0], //Never reminded yet
+    )?;
+    Ok(())
+}
+
+//Function to opt a discord out of inactivity reminders (returns false if not opted in)
+pub fn remove_reminder(conn: &Connection, discord_id: &str) -> Result<bool> {
+    let removed = conn.execute("DELETE FROM reminders WHERE discord = ?1", params![discord_id])?;
+    Ok(removed > 0)
+}
+
+//Fetch every reminder of a discord that is still linked
+pub fn fetch_reminders(conn: &Connection) -> Result<Vec<Reminder>> {
+    let mut stmt = conn.prepare(
+        "SELECT reminders.discord, reminders.user, reminders.channel, reminders.days, reminders.reminded, player_data.id FROM reminders
+        JOIN player_data ON player_data.discord = reminders.discord",
+    )?;
+    let reminders = stmt.query_map(params![], |row| {
+        Ok(Reminder {
+            discord: row.get(0)?,
+            user: row.get(1)?,
+            channel: row.get(2)?,
+            days: row.get(3)?,
+            reminded: row.get(4)?,
+            player: row.get(5)?,
+        })
+    })?;
+    reminders.collect()
+}
+
+//Function to remember when a discord was last reminded
+pub fn set_reminded(conn: &Connection, discord_id: &str, time: i64) -> Result<()> {
+    conn.execute("UPDATE reminders SET reminded = ?1 WHERE discord = ?2", params![time, discord_id])?;
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use crate::Changes; //Changes struct
+use crate::imports::*; //Imports
+use crate::datatweaks::{create_db, delete_field_selection, fetch_field_selection, save_field_selection};
+use crate::message::send_simple_format; //Function to send a simple embedded message
+use crate::metrics::{self, Metric};
+use crate::locale; //Language of the replies
+
+//Function to find the stats a user picked for the stats embed (None if they did not pick any)
+pub fn for_user(discord_id: &str) -> Option<Vec<&'static Metric>> {
+    let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database (tables are created at startup)
+    let names = fetch_field_selection(&conn, discord_id).ok()??;
+    let fields: Vec<&'static Metric> = names.split(',').filter_map(metrics::find).collect();
+    if fields.is_empty() { None } else { Some(fields) }
+}
+
+//Function to find the stats picked by the author of a message
+pub fn for_message(msg: &Message) -> Option<Vec<&'static Metric>> {
+    for_user(&msg.author.name)
+}
+
+//Function to check if any of the picked stats changed (every stat counts if none were picked)
+pub fn selected_changed(changes: &Changes, fields: Option<&[&'static Metric]>) -> bool {
+    match fields {
+        Some(fields) => changes.metrics.iter().any(|change| fields.iter().any(|field| field.name == change.metric.name)),
+        None => !changes.metrics.is_empty(),
+    }
+}
+
+//Function to react to the fields command (set, reset, show)
+pub async fn fields_command(ctx: &Context, msg: &Message, parts: &[&str]) {
+    let locale = locale::for_message(msg); //Language of the replies
+    let discord_id = msg.author.name.as_str();
+    let action = parts.first().copied().unwrap_or("show");
+    let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+    let reply = match action {
+        "set" => {
+            let mut names = Vec::new();
+            let mut invalid = Vec::new();
+            for name in &parts[1..] { //Check every stat
+                match metrics::find(name) {
+                    Some(metric) if !names.contains(&metric.name) => names.push(metric.name),
+                    Some(_) => {} //Ignore duplicates
+                    None => invalid.push(*name),
+                }
+            }
+            if !invalid.is_empty() || names.is_empty() { //If a stat does not exist
+                Ok(locale.fill("fields.invalid", &[&invalid.join(" "), &metrics::names()]))
+            } else {
+                create_db(&conn).and_then(|_| save_field_selection(&conn, discord_id, &names.join(",")))
+                    .map(|_| locale.fill("fields.set", &[&names.join(" ")]))
+            }
+        }
+        "reset" => create_db(&conn).and_then(|_| delete_field_selection(&conn, discord_id)).map(|_| locale.text("fields.reset").to_string()),
+        _ => Ok(match create_db(&conn).and_then(|_| fetch_field_selection(&conn, discord_id)) { //Show
+            Ok(Some(names)) => locale.fill("fields.show", &[&names.replace(',', " ")]),
+            _ => format!("{} (`!fields` `set` `stat...`)", locale.text("fields.default")),
+        }),
+    };
+    drop(conn); //Close database before sending
+    match reply {
+        Ok(reply) => {
+            println!("Fields `{}` for discord `{}` (!fields)", action, discord_id); //Print success
+            send_simple_format(ctx, msg, &reply).await; //Send reply
+        }
+        Err(e) => {
+            println!("Error saving fields: {}", e); //Print error
+            send_simple_format(ctx, msg, locale.text("fields.failed")).await; //Send error
+        }
+    }
+}
@@ -1,9 +1,15 @@
 use crate::PlayerData; //PlayerData struct
 use crate::Changes; //Changes struct
+use crate::ProfileEvent; //ProfileEvent enum
+use crate::Snapshot; //Snapshot struct
 use crate::imports::*; //Imports
+use crate::metrics::{self, Metric, METRICS};
+use crate::theme::{Layout, Theme};
+use crate::locale::Locale; //Language and number format
+use crate::datatweaks::current_time; //Function to get the current time
 
-//Function to add commas
-pub fn add_commas(mut num: i64, include_pos: bool) -> String {
+//Function to group the digits of a number in threes (separator is , or . depending on the locale)
+pub fn group_digits(mut num: i64, include_pos: bool, separator: char) -> String {
     let mut result = String::new(); //Sets new string
     let mut count = 0; //Sets up count (Every 3 nums)
     let is_negative = num < 0; // Check if the number is negative
@@ -14,8 +20,8 @@ pub fn add_commas(mut num: i64, include_pos: bool) -> String {
         result.push('0'); // Handle the case where num is 0
     }
     while num > 0 { //while there is still more to the number
-        if count != 0 && count % 3 == 0 { //If the count is divisible by three then add comma
-            result.insert(0, ',');
+        if count != 0 && count % 3 == 0 { //If the count is divisible by three then add separator
+            result.insert(0, separator);
         }
         result.insert(0, (b'0' + (num % 10) as u8) as char); //adds num to answer (result)
         num /= 10; //shifts to next place
@@ -30,79 +36,130 @@ pub fn add_commas(mut num: i64, include_pos: bool) -> String {
     result //return answer
 }
 
-//Function to format data
-pub fn formatdata(data: &PlayerData, changes: &Changes, new_user: bool) -> CreateMessage{ // Name of function and stating return type
-    //Formatting data
-    let mut totalScore_formatted = add_commas(data.scoreStats.totalScore, false); //add commas
-    let mut totalRankedScore_formatted = add_commas(data.scoreStats.totalRankedScore, false); //add commas
-    let mut totalPlayCount_formatted = add_commas(data.scoreStats.totalPlayCount, false); //add commas
-    let mut rankedPlayCount_formatted = add_commas(data.scoreStats.rankedPlayCount, false); //add commas
-    let mut rank_formatted = add_commas(data.rank, false); //add commas
-    let mut countryRank_formatted = add_commas(data.countryRank, false); //add commas
-    let mut replaysWatched_formatted = format!("{}", data.scoreStats.replaysWatched); //Make formatted var
-    let mut pp_formatted = format!("{}", data.pp); //Make formatted var
-    let mut averageRankedAccuracy_formatted = format!("{}", (data.scoreStats.averageRankedAccuracy * 10_000.0).round() / 10_000.0); //Make formatted var (round four decimal places)
-    let firstSeen_formatted = &data.firstSeen[0..10]; //Condencing the firstSeen variable to exclude time
+//Function to turn a country code into a flag emoji (regional indicator letters), anything else is shown as is
+pub fn flag_emoji(country: &str) -> String {
+    if country.len() != 2 || !country.chars().all(|c| c.is_ascii_alphabetic()) { //Flags need a two letter code
+        return country.to_string();
+    }
+    country.to_ascii_uppercase().chars().filter_map(|c| char::from_u32(0x1F1E6 + (c as u32 - 'A' as u32))).collect()
+}
 
-    //Implementing changes to payload if it is not a new user 
-    if !new_user{ //If not new user
-        //Implementing changes
-        if changes.tScore{ //If there is a change
-            totalScore_formatted += &format!("\n`{}`", add_commas(changes.tScore_change, true)); //Add the change after adding commas to the change
-        }
-        if changes.rScore{
-            totalRankedScore_formatted += &format!("\n`{}`", add_commas(changes.rScore_change, true));
-        }
-        if changes.tCount{
-            totalPlayCount_formatted += &format!(" `{}`", add_commas(changes.tCount_change, true));
-        }
-        if changes.rCount{
-            rankedPlayCount_formatted += &format!(" `{}`", add_commas(changes.rCount_change, true));
-        }
-        if changes.replays{
-            replaysWatched_formatted += &format!("\n`{}`", add_commas(changes.replays_change, true));
-        }
-        if changes.rank{
-            rank_formatted += &format!(" `{}`", add_commas(changes.rank_change, true));
-        }
-        if changes.cRank{
-            countryRank_formatted += &format!(" `{}`", add_commas(changes.cRank_change, true));
-        }
-        if changes.pp{
-            pp_formatted += &format!("\n`{}`", changes.pp_change); //Add the change
-        }
-        if changes.rAccuracy{
-            averageRankedAccuracy_formatted += &format!("\n`{}`", (changes.rAccuracy_change * 10_000.0).round() / 10_000.0); //rounds 4 decimal places
-        }
-        //println!("\nExisting User `{}` found! <ID:{}>\n", data.name, data.id); //Print out basic user data
+//Function to make the link to the ScoreSaber profile of a player
+pub fn profile_url(player_id: &str) -> String {
+    format!("https://scoresaber.com/u/{}", player_id)
+}
+
+//Function to name a difficulty (ScoreSaber numbers them 1 to 9)
+pub fn difficulty_name(difficulty: i64) -> &'static str {
+    match difficulty {
+        1 => "Easy",
+        3 => "Normal",
+        5 => "Hard",
+        7 => "Expert",
+        9 => "Expert+",
+        _ => "Unknown",
     }
-    else{ //If new use
-        println!("\nNew User `{}` added to database! <ID:{}>\n", data.name, data.id); //Print out basic user data and lets know user is new
+}
+
+//Function to describe a profile event
+pub fn describe_event(event: &ProfileEvent, locale: Locale) -> String {
+    match event {
+        ProfileEvent::Inactive(true) => locale.text("event.inactive").to_string(),
+        ProfileEvent::Inactive(false) => locale.text("event.active").to_string(),
+        ProfileEvent::Banned(true) => locale.text("event.banned").to_string(),
+        ProfileEvent::Banned(false) => locale.text("event.unbanned").to_string(),
+        ProfileEvent::Renamed { old, new } => locale.fill("event.renamed", &[old, new]),
+        ProfileEvent::CountryChanged { old, new } => locale.fill("event.country", &[old, new]),
+        ProfileEvent::AvatarChanged => locale.text("event.avatar").to_string(),
+    }
+}
+
+//Stats shown in the grid of the stats embed (two per row)
+const GRID: [&str; 6] = ["totalScore", "rankedScore", "acc", "pp", "rankedPlays", "totalPlays"];
+
+//Function to format the value of a metric followed by its change (if there is one)
+fn format_metric(stats: &Snapshot, changes: &Changes, name: &str, separator: &str, locale: Locale) -> String {
+    let metric = match metrics::find(name) {
+        Some(metric) => metric,
+        None => return String::new(), //Unknown metric
+    };
+    let mut formatted = metric.display((metric.value)(stats), locale); //Format the value
+    if let Some(change) = changes.get(name) { //If there is a change
+        formatted += &format!("{}`{}`", separator, metric.format_change(change.old, change.new, locale)); //Add the change (styled by whether it is an improvement)
     }
+    formatted
+}
 
-    //If-statement to make my own embed message look dark red
-    let mut my_color = 0; //Set default color to black
-    if data.name == "ColGuy20"{ //If it is me
-        my_color = 5505024; //Change color to dark red
+//Function to format data (fields are the stats picked by the user, None shows the defaults of the layout)
+#[allow(clippy::useless_format)] //Original profile picture formatting
+pub fn formatdata(data: &PlayerData, changes: &Changes, new_user: bool, theme: &Theme, locale: Locale, fields: Option<&[&'static Metric]>) -> CreateMessage{ // Name of function and stating return type
+    let stats = data.snapshot(); //Numeric stats of the player
+    let firstSeen_formatted = match DateTime::parse_from_rfc3339(&data.firstSeen) { //Parse the firstSeen timestamp
+        Ok(date) => { //Discord shows it relative to the reader ("3 years ago") plus the account age
+            let age = (current_time() - date.timestamp()).max(0) / 86_400;
+            format!("<t:{}:R> ({})", date.timestamp(), locale.fill("stats.account_age", &[&locale.integer(age, false)]))
+        }
+        Err(_) => String::from("?"), //Missing or invalid timestamp
+    };
+
+    if new_user { //If new user
+        println!("\nNew User `{}` added to database! <ID:{}>\n", data.name, data.id); //Print out basic user data and lets know user is new
     }
-    
+
+    //Stats to show below the description (ranks are part of the description)
+    let shown: Vec<&'static Metric> = match fields {
+        Some(fields) => fields.iter().copied().filter(|metric| metric.name != "rank" && metric.name != "countryRank").collect(),
+        None if theme.layout == Layout::Detailed => METRICS.iter().filter(|metric| metric.name != "rank" && metric.name != "countryRank").collect(),
+        None => GRID.iter().filter_map(|name| metrics::find(name)).collect(),
+    };
+    let picked = |name: &str| fields.is_none_or(|fields| fields.iter().any(|metric| metric.name == name)); //If a rank should be in the description
+
     //Making the payload (Embed message into CreateEmbed)
-    let payload = CreateMessage::new().embed(CreateEmbed::new() //Create message
-        .color(my_color) //Set color
-        .field("Description", format!("Rank: **#{}**\nCountry Rank ({}): **#{}**\nFirst Seen: {}", rank_formatted, data.country, countryRank_formatted, firstSeen_formatted), false) //Rank, Country/Rank, and date first seen
-        .field("", "", false) //These are just for cosmetic purposes, they ensure that only two fields are in each row
-        .field("Total Score", format!("{}", totalScore_formatted), true) //Total Score
-        .field("Total Ranked Score", format!("{}", totalRankedScore_formatted), true) //Total Ranked Score
-        .field("", "", false) //These are just for cosmetic purposes, they ensure that only two fields are in each row
-        .field("Average Ranked Accuracy", format!("%{}", averageRankedAccuracy_formatted), true) //Average Ranked Accuracy
-        .field("Performance Point (PP)", format!("{}", pp_formatted), true) //Performance Points
-        .field("", "", false) //These are just for cosmetic purposes, they ensure that only two fields are in each row
-        .field("Ranked Play Count", format!("{} ", rankedPlayCount_formatted), true) //Ranked Play Count
-        .field("Total Play Count", format!("{} ", totalPlayCount_formatted), true) //Total Play Count
+    let mut description = Vec::new();
+    if picked("rank") {
+        description.push(format!("{}: **#{}**", locale.text("stats.rank"), format_metric(&stats, changes, "rank", " ", locale))); //Rank
+    }
+    if picked("countryRank") {
+        description.push(format!("{} ({}): **#{}**", locale.text("stats.country_rank"), flag_emoji(&data.country), format_metric(&stats, changes, "countryRank", " ", locale))); //Country/Rank
+    }
+    description.push(format!("{}: {}", locale.text("stats.first_seen"), firstSeen_formatted)); //Date first seen
+    let description = description.join("\n");
+    let mut embed = CreateEmbed::new() //Create embed
+        .color(theme.color) //Set color
         .author(
             CreateEmbedAuthor::new("Temp") //Add embedded author
                 .name(format!("{} #{}", data.name, data.rank)) //Username and global rank
                 .icon_url(format!("{}",data.profilePicture)) //Profile Picture
-        ));
-    payload //Return embedded payload
-}
\ No newline at end of file
+                .url(profile_url(&data.id)) //Link to the ScoreSaber profile
+        );
+    match theme.layout {
+        Layout::Grid => { //Two stats per row
+            embed = embed.field(locale.text("stats.description"), description, false);
+            for (position, metric) in shown.iter().enumerate() { //Add every stat of the grid
+                if position % 2 == 0 { //These are just for cosmetic purposes, they ensure that only two fields are in each row
+                    embed = embed.field("", "", false);
+                }
+                embed = embed.field(locale.label(metric), format_metric(&stats, changes, metric.name, "\n", locale), true);
+            }
+        }
+        Layout::Detailed => { //Every stat in its own row
+            embed = embed.field(locale.text("stats.description"), description, false);
+            for metric in &shown { //Add every stat
+                embed = embed.field(locale.label(metric), format_metric(&stats, changes, metric.name, " ", locale), false);
+            }
+        }
+        Layout::Compact => { //Everything in a single field
+            let mut lines = vec![description];
+            for metric in &shown { //Add every stat
+                lines.push(format!("{}: **{}**", locale.label(metric), format_metric(&stats, changes, metric.name, " ", locale)));
+            }
+            embed = embed.field(locale.text("stats.stats"), lines.join("\n"), false);
+        }
+    }
+    if !new_user && !changes.events.is_empty() { //If the profile changed
+        let events: Vec<String> = changes.events.iter().map(|event| describe_event(event, locale)).collect();
+        println!("Profile changes for `{}`: {}", data.name, events.join(", ")); //Print profile changes
+        embed = embed.field(locale.text("stats.profile_changes"), events.join("\n"), false); //List of profile changes
+    }
+    CreateMessage::new().embed(embed) //Return embedded payload
+}
@@ -0,0 +1,270 @@
+use crate::PlayerData; //PlayerData struct
+use crate::Snapshot; //Snapshot struct
+use crate::Goal; //Goal struct
+use crate::imports::*; //Imports
+use crate::datatweaks::{create_db, current_time, fetch_goals, fetch_linked_discord, fetch_player_data_from_db, fetch_snapshots_since, remove_goal, set_goal, set_goal_reached};
+use crate::message::{send_announcement, send_simple_format};
+use crate::metrics::{self, Metric};
+use crate::theme::{self, Theme};
+use crate::locale::{self, Locale};
+
+const PROJECTION_WINDOW: i64 = 2_592_000; //Snapshots of the last 30 days are used for projections
+const BAR_LENGTH: usize = 10; //Amount of blocks in the progress bar
+
+//Function to make a progress bar (fraction from 0 to 1)
+fn progress_bar(fraction: f64) -> String {
+    let fraction = fraction.clamp(0.0, 1.0);
+    let filled = (fraction * BAR_LENGTH as f64).round() as usize;
+    format!("`{}{}` {:.0}%", "▓".repeat(filled), "░".repeat(BAR_LENGTH - filled), fraction * 100.0)
+}
+
+//Function to project when a target is reached with a linear fit of the snapshots (None if not getting closer)
+fn project(snapshots: &[Snapshot], metric: &Metric, target: f64) -> Option<i64> {
+    let points: Vec<(f64, f64)> = snapshots.iter()
+        .map(|snapshot| (snapshot.timestamp as f64, (metric.value)(snapshot)))
+        .filter(|(_, value)| metric.is_ranked(*value)) //Inactive players have a rank of 0
+        .collect();
+    if points.len() < 2 { //Not enough history
+        return None;
+    }
+    //Least squares fit of value = intercept + slope * time
+    let count = points.len() as f64;
+    let mean_time = points.iter().map(|(time, _)| time).sum::<f64>() / count;
+    let mean_value = points.iter().map(|(_, value)| value).sum::<f64>() / count;
+    let covariance: f64 = points.iter().map(|(time, value)| (time - mean_time) * (value - mean_value)).sum();
+    let variance: f64 = points.iter().map(|(time, _)| (time - mean_time).powi(2)).sum();
+    if variance == 0.0 { //All snapshots at the same time
+        return None;
+    }
+    let slope = covariance / variance;
+    if metric.improvement(0.0, slope) <= 0.0 { //Not getting closer to the target
+        return None;
+    }
+    let time = mean_time + (target - mean_value) / slope; //Time where the fit reaches the target
+    Some((time as i64).max(current_time()))
+}
+
+//Function to describe the progress of a goal
+fn describe_goal(goal: &Goal, metric: &Metric, value: f64, snapshots: &[Snapshot], locale: Locale) -> String {
+    let mut description = locale.fill("goal.target", &[&metric.display(goal.target, locale), &metric.display(value, locale)]);
+    if let Some(reached) = goal.reached { //If already reached
+        description += &format!("\n{}", locale.fill("goal.reached_on", &[&locale.date(reached)]));
+        return description;
+    }
+    description += &format!("\n{}", progress_bar((value - goal.start) / (goal.target - goal.start)));
+    match project(snapshots, metric, goal.target) { //Projected completion date
+        Some(time) => {
+            description += &format!("\n{}", locale.fill("goal.projected", &[&locale.date(time)]));
+            if let Some(deadline) = goal.deadline {
+                description += &format!(" ({})", locale.text(if time > deadline { "goal.behind" } else { "goal.on_track" }));
+            }
+        }
+        None => description += &format!("\n{}", locale.text("goal.no_projection")),
+    }
+    if let Some(deadline) = goal.deadline {
+        description += &format!("\n{}", locale.fill("goal.deadline", &[&locale.date(deadline)]));
+    }
+    description
+}
+
+//Function to make the goals embed of a player (None if the player is not linked or has no goals)
+pub fn goals_embed(data: &PlayerData, theme: &Theme, locale: Locale) -> Option<CreateEmbed> {
+    let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+    let discord_id = fetch_linked_discord(&conn, &data.id).ok()??; //Goals belong to linked discords
+    let goals = fetch_goals(&conn, &discord_id).ok()?;
+    if goals.is_empty() { //Nothing to show
+        return None;
+    }
+    let snapshots = fetch_snapshots_since(&conn, &data.id, current_time() - PROJECTION_WINDOW).unwrap_or_default();
+    let mut embed = CreateEmbed::new() //Make Embed
+        .color(theme.accent) //Set color to the accent of the theme
+        .title(locale.text("goal.title")); //Set title
+    let stats = data.snapshot(); //Numeric stats of the player
+    let mut shown = 0;
+    for goal in goals { //Loop through every goal
+        if let Some(metric) = metrics::find(&goal.metric) {
+            let value = (metric.value)(&stats);
+            if !metric.is_ranked(value) { //Inactive players have a rank of 0 so rank goals have no progress
+                continue;
+            }
+            embed = embed.field(locale.label(metric), describe_goal(&goal, metric, value, &snapshots, locale), false);
+            shown += 1;
+        }
+    }
+    if shown == 0 { //Every goal was skipped
+        return None;
+    }
+    Some(embed)
+}
+
+//Function to find the goals a player just reached (marks them as reached)
+fn find_reached_goals(data: &PlayerData) -> Result<Vec<Goal>> {
+    let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+    create_db(&conn)?; //Create database if not exists
+    let discord_id = match fetch_linked_discord(&conn, &data.id)? { //Goals belong to linked discords
+        Some(discord_id) => discord_id,
+        None => return Ok(Vec::new()),
+    };
+    let stats = data.snapshot(); //Numeric stats of the player
+    let mut reached = Vec::new();
+    for goal in fetch_goals(&conn, &discord_id)? { //Loop through every goal
+        let metric = match metrics::find(&goal.metric) {
+            Some(metric) => metric,
+            None => continue, //Unknown metric
+        };
+        let value = (metric.value)(&stats);
+        if !metric.is_ranked(value) { //Inactive players have a rank of 0
+            continue;
+        }
+        if goal.reached.is_none() && metric.reached(value, goal.target) { //If reached for the first time
+            set_goal_reached(&conn, &discord_id, &goal.metric)?;
+            reached.push(goal);
+        }
+    }
+    Ok(reached)
+}
+
+//Function to congratulate a player on every goal reached during a refresh
+pub async fn announce_goals(ctx: &Context, msg: &Message, data: &PlayerData) {
+    let reached = match find_reached_goals(data) {
+        Ok(reached) => reached,
+        Err(e) => {
+            println!("Error checking goals: {}", e); //Print error
+            return;
+        }
+    };
+    let locale = locale::for_message(msg); //Number and date format
+    for goal in reached { //Loop through every reached goal
+        let embed = CreateEmbed::new() //Make Embed
+            .color(theme::for_message(msg).accent) //Set color to the accent of the theme
+            .title(locale.text("goal.reached_title")) //Set title
+            .description(locale.fill("goal.reached", &[&data.name, &metrics::find(&goal.metric).map(|metric| metric.display(goal.target, locale)).unwrap_or_default(), &locale.date(goal.created)]));
+        match msg.guild_id {
+            Some(guild_id) => send_announcement(ctx, &guild_id.to_string(), Some(msg.channel_id), embed).await,
+            None => { //If refreshed outside of a server then send it right here
+                if let Err(why) = msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await { // If sending message has error
+                    println!("Error sending message: {:?}", why); // Print error
+                }
+            }
+        }
+        println!("{} reached their goal of {} {}", data.name, goal.target, goal.metric); //Print goal
+    }
+}
+
+//Function to parse a deadline (YYYY-MM-DD) into the end of that day
+fn parse_deadline(deadline: &str) -> Option<i64> {
+    let date = NaiveDate::parse_from_str(deadline, "%Y-%m-%d").ok()?;
+    Some(date.and_hms_opt(23, 59, 59)?.and_utc().timestamp())
+}
+
+//Function to react to the goal command (set, remove, list)
+pub async fn goal_command(ctx: &Context, msg: &Message, parts: &[&str], linked_id: &str) {
+    let locale = locale::for_message(msg); //Language and number format
+    if linked_id.is_empty() { //Goals belong to linked players
+        send_simple_format(ctx, msg, format!("{} (`!link` `player_id`)", locale.text("remind.link_first")).as_str()).await; //Send error
+        return;
+    }
+    let discord_id = msg.author.name.as_str();
+    match parts.first().copied().unwrap_or("") {
+        "set" => {
+            let metric = metrics::find(parts.get(1).copied().unwrap_or(""));
+            let target = parts.get(2).and_then(|target| target.parse::<f64>().ok());
+            let deadline = parts.get(3).map(|deadline| parse_deadline(deadline));
+            let (metric, target, deadline) = match (metric, target, deadline) { //Parse the goal
+                (Some(metric), Some(target), None) if target > 0.0 => (metric, target, None),
+                (Some(metric), Some(target), Some(Some(deadline))) if target > 0.0 && deadline > current_time() => (metric, target, Some(deadline)),
+                _ => {
+                    send_simple_format(ctx, msg, format!("{} (`!goal` `set` `{}` `target` `YYYY-MM-DD [OPTIONAL]`)", locale.text("goal.invalid"), metrics::names()).as_str()).await; //Send error
+                    return;
+                }
+            };
+            let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+            let result = create_db(&conn).and_then(|_| fetch_player_data_from_db(&conn, linked_id)).and_then(|data| {
+                let value = (metric.value)(&data.snapshot());
+                if metric.reached(value, target) { //Goal has to be ahead of the player
+                    return Ok(None);
+                }
+                set_goal(&conn, discord_id, &Goal { metric: metric.name.to_string(), target, start: value, created: current_time(), deadline, reached: None })?;
+                Ok(Some(value))
+            });
+            drop(conn); //Close database before sending
+            match result {
+                Ok(Some(value)) => {
+                    println!("Discord `{}` set a goal of {} {} (!goal)", discord_id, target, metric.name); //Print success
+                    send_simple_format(ctx, msg, locale.fill("goal.set", &[&metric.display(target, locale), &metric.display(value, locale)]).as_str()).await; //Send success
+                }
+                Ok(None) => send_simple_format(ctx, msg, locale.text("goal.already_reached")).await, //Send error
+                Err(e) => {
+                    println!("Error setting goal: {}", e); //Print error
+                    send_simple_format(ctx, msg, locale.text("goal.set_failed")).await; //Send error
+                }
+            }
+        }
+        "remove" => {
+            let metric = metrics::find(parts.get(1).copied().unwrap_or("")).map(|metric| metric.name).unwrap_or("");
+            let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+            let result = create_db(&conn).and_then(|_| remove_goal(&conn, discord_id, metric));
+            drop(conn); //Close database before sending
+            match result {
+                Ok(true) => send_simple_format(ctx, msg, locale.fill("goal.removed", &[metric]).as_str()).await, //Send success
+                Ok(false) => send_simple_format(ctx, msg, format!("{} (`!goal` `list`)", locale.text("goal.not_found")).as_str()).await, //Send error
+                Err(e) => {
+                    println!("Error removing goal: {}", e); //Print error
+                    send_simple_format(ctx, msg, locale.text("goal.remove_failed")).await; //Send error
+                }
+            }
+        }
+        _ => { //List
+            let embed = {
+                let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+                fetch_player_data_from_db(&conn, linked_id).ok().and_then(|data| goals_embed(&data, &theme::for_message(msg), locale))
+            };
+            match embed {
+                Some(embed) => {
+                    if let Err(why) = msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await { // If sending message has error
+                        println!("Error sending message: {:?}", why); // Print error
+                    }
+                }
+                None => send_simple_format(ctx, msg, format!("{} (`!goal` `set` `metric` `target`)", locale.text("goal.none")).as_str()).await, //Send error
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //Function to make a snapshot some days before now
+    fn snapshot(days_ago: i64, pp: f64, rank: i64) -> Snapshot {
+        Snapshot { timestamp: current_time() - days_ago * 86_400, pp, rank, ..Default::default() }
+    }
+
+    #[test]
+    fn progress_bar_fills_and_clamps() {
+        assert_eq!(progress_bar(0.0), "`░░░░░░░░░░` 0%");
+        assert_eq!(progress_bar(0.5), "`▓▓▓▓▓░░░░░` 50%");
+        assert_eq!(progress_bar(1.5), "`▓▓▓▓▓▓▓▓▓▓` 100%");
+        assert_eq!(progress_bar(-0.5), "`░░░░░░░░░░` 0%");
+    }
+
+    #[test]
+    fn project_extends_the_trend_to_the_target() {
+        let pp = metrics::find("pp").unwrap();
+        let snapshots = vec![snapshot(2, 100.0, 5000), snapshot(1, 110.0, 4000)];
+        let projected = project(&snapshots, pp, 130.0).unwrap(); //10pp a day, 20pp to go since yesterday
+        assert!((projected - (current_time() + 86_400)).abs() < 5);
+        let rank = metrics::find("rank").unwrap(); //Lower rank is better
+        let projected = project(&snapshots, rank, 2000.0).unwrap(); //1000 ranks a day, 2000 to go since yesterday
+        assert!((projected - (current_time() + 86_400)).abs() < 5);
+    }
+
+    #[test]
+    fn project_needs_progress_and_history() {
+        let pp = metrics::find("pp").unwrap();
+        assert_eq!(project(&[snapshot(1, 100.0, 5000)], pp, 130.0), None);
+        assert_eq!(project(&[snapshot(2, 110.0, 5000), snapshot(1, 100.0, 5000)], pp, 130.0), None); //Losing pp
+        let rank = metrics::find("rank").unwrap();
+        assert_eq!(project(&[snapshot(2, 100.0, 0), snapshot(1, 100.0, 4000)], rank, 2000.0), None); //Unranked snapshots are left out
+    }
+}
@@ -5,12 +5,14 @@ pub use reqwest::Error; //Used for handling errors related to HTTP requests
 pub use tokio::time::{sleep, Duration}; //Used for async/await and time-based operations such as sleep
 pub use std::env; //Used for interacting with environmental variables
 pub use rusqlite::{params, Connection, Result}; //Used to integrate database (SQLite) functions
-pub use std::collections::HashMap; //Used to create instances without needing to write the whole path each time
+pub use std::collections::{HashMap, HashSet}; //Used to create instances without needing to write the whole path each time
+pub use chrono::{DateTime, NaiveDate, Utc}; //Used for parsing and formatting dates
 
 //Imports needed for discord bot
 pub use serenity::{
     model::{
         channel::{Message}, //Message sent in channel 
+        id::{ChannelId, GuildId}, //ID of a channel (used for announcements) and of a guild (used for member lists)
         gateway::{
             GatewayIntents, //Events the bot is seeking
             Ready //Event for when bot is ready
@@ -19,8 +21,11 @@ pub use serenity::{
     builder::{
         CreateEmbed, //Create embeds
         CreateEmbedAuthor, //Create embedded author
+        CreateEmbedFooter, //Create embedded footer
+        CreateAttachment, //Create attachments (images)
         CreateMessage //Create messaged (can be embed)
     },
+    Error as SerenityError, //Errors from discord requests
     async_trait, // Provides support for async traits
     prelude::* // Commonly used traits and types from serenity
 };
\ No newline at end of file
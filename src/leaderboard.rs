@@ -0,0 +1,213 @@
+use crate::Snapshot; //Snapshot struct
+use crate::imports::*; //Imports
+use crate::datatweaks::{create_db, current_time, fetch_guild_discords, fetch_guild_player_ids, fetch_player_data_from_db, fetch_snapshot_before, fetch_snapshot_since, last_updated, refresh_players};
+use crate::message::send_simple_format; //Function to send a simple embedded message
+use crate::metrics::{self, Metric};
+use crate::schedule::WEEK; //Seconds in a week
+use crate::theme; //Embed themes
+use crate::locale::{self, Locale};
+
+const STALE_AFTER: i64 = 3600; //Players not updated within an hour get refreshed
+const MAX_ROWS: usize = 20; //Amount of players shown in the leaderboard
+const MAX_CLIMBERS: usize = 3; //Amount of players shown in each category of the weekly climbers
+const CLIMBER_METRICS: [&str; 4] = ["pp", "rank", "countryRank", "rankedPlays"]; //Categories of the weekly climbers
+
+//What a leaderboard can be sorted by
+#[derive(Clone, Copy)]
+enum Sort {
+    Metric(&'static Metric),
+    WeeklyGain,
+}
+
+//One row of the leaderboard
+struct Entry {
+    name: String,
+    discord: String,
+    stats: Snapshot,
+    weekly_gain: f64,
+}
+
+//Stats of one player at the start and end of a week
+struct Climber {
+    name: String,
+    first: Snapshot,
+    last: Snapshot,
+}
+
+//Function to turn the parameter into a sort (default is pp)
+fn parse_sort(param: &str) -> Option<Sort> {
+    match param.to_lowercase().as_str() {
+        "" => metrics::find("pp").map(Sort::Metric),
+        "weeklygain" => Some(Sort::WeeklyGain),
+        _ => metrics::find(param).map(Sort::Metric),
+    }
+}
+
+//Function to collect the cached rows of the linked players in a guild (returns the entries and the stale ids)
+fn collect_entries(discords: &HashSet<String>) -> Result<(Vec<Entry>, Vec<String>)> {
+    let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+    create_db(&conn)?; //Create database if not exists
+
+    let now = current_time();
+    let mut entries = Vec::new();
+    let mut stale = Vec::new();
+    for (player_id, discord) in fetch_guild_player_ids(&conn, discords)? { //Loop through every linked player
+        let data = fetch_player_data_from_db(&conn, &player_id)?;
+        match last_updated(&conn, &player_id)? { //Check when the row was last updated
+            Some(time) if now - time < STALE_AFTER => {}
+            _ => stale.push(player_id.clone()), //Row is stale (or has never been snapshotted)
+        }
+        let weekly_gain = match fetch_snapshot_since(&conn, &player_id, now - WEEK)? { //Oldest snapshot of the week
+            Some(snapshot) => data.pp - snapshot.pp,
+            None => 0.0,
+        };
+        entries.push(Entry { name: data.name.clone(), discord, stats: data.snapshot(), weekly_gain });
+    }
+    Ok((entries, stale))
+}
+
+//Function to find the value an entry is sorted by (higher is always better, unranked players go last)
+fn sort_key(entry: &Entry, sort: Sort) -> f64 {
+    match sort {
+        Sort::WeeklyGain => entry.weekly_gain,
+        Sort::Metric(metric) => {
+            let value = (metric.value)(&entry.stats);
+            if !metric.is_ranked(value) { //Inactive players have a rank of 0
+                f64::NEG_INFINITY
+            } else if metric.higher_is_better {
+                value
+            } else {
+                -value
+            }
+        }
+    }
+}
+
+//Function to format the stat an entry is ranked by
+fn format_value(entry: &Entry, sort: Sort, locale: Locale) -> String {
+    match sort {
+        Sort::WeeklyGain => format!("{}{}pp", if entry.weekly_gain >= 0.0 { "+" } else { "" }, locale.decimal(entry.weekly_gain, 2)),
+        Sort::Metric(metric) => metric.display((metric.value)(&entry.stats), locale),
+    }
+}
+
+//Function to send the leaderboard of the linked players in the guild
+pub async fn send_leaderboard(ctx: &Context, msg: &Message, param: &str) {
+    let locale = locale::for_message(msg); //Language and number format
+    let guild_id = match msg.guild_id { //Leaderboards only exist inside of a server
+        Some(guild_id) => guild_id,
+        None => {
+            send_simple_format(ctx, msg, locale.text("leaderboard.guild_only")).await; //Send error
+            return;
+        }
+    };
+    let sort = match parse_sort(param) { //Parse the sort
+        Some(sort) => sort,
+        None => {
+            send_simple_format(ctx, msg, format!("{} (`!leaderboard` `{}|weeklyGain`)", locale.text("leaderboard.invalid_sort"), metrics::names()).as_str()).await; //Send error
+            return;
+        }
+    };
+
+    let discords = match fetch_guild_discords(ctx, guild_id).await { //Members of the guild
+        Ok(discords) => discords,
+        Err(e) => {
+            println!("Error fetching guild members: {}", e); //Print error
+            send_simple_format(ctx, msg, locale.text("leaderboard.members_failed")).await; //Send error
+            return;
+        }
+    };
+    let (mut entries, stale) = match collect_entries(&discords) { //Collect the cached rows
+        Ok(result) => result,
+        Err(e) => {
+            println!("Error fetching leaderboard from database: {}", e); //Print error
+            send_simple_format(ctx, msg, locale.text("leaderboard.failed")).await; //Send error
+            return;
+        }
+    };
+    if entries.is_empty() { //If nobody in the guild is linked
+        send_simple_format(ctx, msg, format!("{} (`!link` `player_id`)", locale.text("leaderboard.empty")).as_str()).await; //Send error
+        return;
+    }
+    entries.sort_by(|a, b| sort_key(b, sort).total_cmp(&sort_key(a, sort)));
+
+    //Making the description (one line per player)
+    let mut description = String::new();
+    for (position, entry) in entries.iter().take(MAX_ROWS).enumerate() {
+        description += &format!("**{}.** {} (`{}`) - **{}**\n", position + 1, entry.name, entry.discord, format_value(entry, sort, locale));
+    }
+    let title = match sort {
+        Sort::WeeklyGain => locale.text("leaderboard.weekly_gain"),
+        Sort::Metric(metric) => locale.label(metric),
+    };
+    let mut embed = CreateEmbed::new() //Make Embed
+        .color(theme::for_message(msg).color) //Set color to the theme
+        .title(locale.fill("leaderboard.title", &[title])) //Set title
+        .description(description); //Set description to the leaderboard
+    if !stale.is_empty() { //If some rows are stale
+        embed = embed.footer(CreateEmbedFooter::new(locale.fill("leaderboard.refreshing", &[&stale.len().to_string()])));
+        tokio::spawn(refresh_players(stale)); //Refresh stale rows without blocking the reply
+    }
+    if let Err(why) = msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await { // If sending message has error
+        println!("Error sending message: {:?}", why); // Print error
+    }
+    println!("Leaderboard sent to Discord (!leaderboard {})", param); //Print success
+}
+
+//Function to collect the first and last snapshot of the linked players in a guild between two times
+fn collect_climbers(discords: &HashSet<String>, start: i64, end: i64) -> Result<Vec<Climber>> {
+    let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+    create_db(&conn)?; //Create database if not exists
+
+    let mut climbers = Vec::new();
+    for (player_id, _) in fetch_guild_player_ids(&conn, discords)? { //Loop through every linked player
+        let first = fetch_snapshot_since(&conn, &player_id, start)?; //First snapshot of the week
+        let last = fetch_snapshot_before(&conn, &player_id, end)?; //Last snapshot of the week
+        if let (Some(first), Some(last)) = (first, last) { //If the player was seen during the week
+            if first.timestamp >= last.timestamp { //Only one snapshot so nothing to compare
+                continue;
+            }
+            climbers.push(Climber { name: fetch_player_data_from_db(&conn, &player_id)?.name, first, last });
+        }
+    }
+    Ok(climbers)
+}
+
+//Function to find how much a climber gained in a metric (0 if they were unranked)
+fn gain(climber: &Climber, metric: &Metric) -> f64 {
+    let first = (metric.value)(&climber.first);
+    let last = (metric.value)(&climber.last);
+    if metric.is_ranked(first) && metric.is_ranked(last) { metric.improvement(first, last) } else { 0.0 }
+}
+
+//Function to format the top climbers of one metric (only positive gains are shown)
+fn format_climbers(climbers: &mut [Climber], metric: &Metric, locale: Locale) -> String {
+    climbers.sort_by(|a, b| gain(b, metric).total_cmp(&gain(a, metric)));
+    let mut lines = String::new();
+    for (position, climber) in climbers.iter().filter(|climber| gain(climber, metric) > 0.0).take(MAX_CLIMBERS).enumerate() {
+        lines += &format!("**{}.** {} - **{}**\n", position + 1, climber.name, metric.format_improvement(gain(climber, metric), locale));
+    }
+    if lines.is_empty() { //If nobody gained anything
+        lines.push_str(locale.text("climbers.nobody"));
+    }
+    lines
+}
+
+//Function to make the weekly biggest climbers embed (None if nobody was seen during the week)
+pub fn weekly_climbers(guild_id: &str, discords: &HashSet<String>, start: i64, end: i64) -> Result<Option<CreateEmbed>> {
+    let mut climbers = collect_climbers(discords, start, end)?;
+    if climbers.is_empty() { //If there is no history for the week
+        return Ok(None);
+    }
+    let locale = locale::resolve(None, Some(guild_id)); //Number format of the guild
+    let mut embed = CreateEmbed::new() //Make Embed
+        .color(theme::resolve(None, Some(guild_id)).color) //Set color to the theme of the guild
+        .title(locale.text("climbers.title")); //Set title
+    for (position, metric) in CLIMBER_METRICS.iter().filter_map(|name| metrics::find(name)).enumerate() { //Add every category
+        if position % 2 == 0 && position > 0 { //These are just for cosmetic purposes, they ensure that only two fields are in each row
+            embed = embed.field("", "", false);
+        }
+        embed = embed.field(locale.fill("climbers.field", &[locale.label(metric)]), format_climbers(&mut climbers, metric, locale), true);
+    }
+    Ok(Some(embed))
+}
@@ -0,0 +1,527 @@
+use crate::imports::*; //Imports
+use crate::datatweaks::{create_db, delete_locale, fetch_locale, save_locale};
+use crate::format::group_digits; //Function to group the digits of a number
+use crate::message::{can_manage_guild, send_simple_format}; //Functions to check server permissions and send a simple embedded message
+
+//Languages the bot can reply in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Locale {
+    En,
+    De,
+    Es,
+}
+
+//Default locale when neither the user nor the guild picked one
+const DEFAULT_LOCALE: Locale = Locale::En;
+
+//Message catalog (key, [English, German, Spanish]), {} is replaced by the arguments in order
+const CATALOG: &[(&str, [&str; 3])] = &[
+    //Errors
+    ("error.fields", ["Incorrect number of fields.", "Falsche Anzahl an Feldern.", "Número incorrecto de campos."]),
+    ("error.if_linked", ["If linked use:", "Wenn verknüpft:", "Si está vinculado:"]),
+    ("error.invalid_id", ["Invalid player ID provided.", "Ungültige Spieler-ID angegeben.", "ID de jugador no válida."]),
+    ("error.invalid_command", ["Invalid Command", "Ungültiger Befehl", "Comando no válido"]),
+    ("error.fetch_scores", ["Failed to fetch the scores. Please try again.", "Die Scores konnten nicht abgerufen werden. Bitte versuche es erneut.", "No se pudieron obtener las puntuaciones. Por favor inténtalo de nuevo."]),
+    ("error.load_scores", ["Failed to load the scores. Please try again.", "Die Scores konnten nicht geladen werden. Bitte versuche es erneut.", "No se pudieron cargar las puntuaciones. Por favor inténtalo de nuevo."]),
+    ("error.fetch_ranking", ["Failed to fetch the ranking. Please try again.", "Die Rangliste konnte nicht abgerufen werden. Bitte versuche es erneut.", "No se pudo obtener la clasificación. Por favor inténtalo de nuevo."]),
+    ("error.manage_guild", ["You need the Manage Server permission to change the settings of this server.", "Du brauchst die Berechtigung „Server verwalten“, um die Einstellungen dieses Servers zu ändern.", "Necesitas el permiso Gestionar servidor para cambiar la configuración de este servidor."]),
+    //Track
+    ("track.min", ["Please use a number over 5 seconds.", "Bitte verwende eine Zahl über 5 Sekunden.", "Por favor usa un número mayor a 5 segundos."]),
+    ("track.nan", ["Please use a number for **time**. Make sure it is positive.", "Bitte verwende eine Zahl für **Zeit**. Sie muss positiv sein.", "Por favor usa un número para **tiempo**. Asegúrate de que sea positivo."]),
+    ("track.gap", ["Please use a session gap from 1 to {} minutes, like `30m`.", "Bitte verwende eine Session-Pause von 1 bis {} Minuten, z. B. `30m`.", "Por favor usa una pausa de sesión de 1 a {} minutos, como `30m`."]),
+    ("track.began", ["Began tracking every {} seconds!", "Tracking gestartet, alle {} Sekunden!", "¡Seguimiento iniciado cada {} segundos!"]),
+    ("track.failed", ["FAILED:", "FEHLGESCHLAGEN:", "FALLIDO:"]),
+    ("untrack.stopped", ["Tracking has stopped!", "Tracking wurde gestoppt!", "¡El seguimiento se ha detenido!"]),
+    ("untrack.not_tracking", ["Not currently tracking!", "Derzeit wird nichts getrackt!", "¡No se está siguiendo a nadie!"]),
+    //Link
+    ("link.linked", ["Account: `{}` has been linked with `{}` (discord)", "Konto: `{}` wurde mit `{}` (Discord) verknüpft", "Cuenta: `{}` ha sido vinculada con `{}` (discord)"]),
+    ("link.invalid", ["Invalid ID provided!", "Ungültige ID angegeben!", "¡ID no válida!"]),
+    ("link.no_account", ["Please generate an account in the database for `{}` by running a stats command!", "Bitte lege mit einem Stats-Befehl ein Konto für `{}` in der Datenbank an!", "¡Por favor crea una cuenta en la base de datos para `{}` ejecutando un comando de estadísticas!"]),
+    ("link.remove_failed", ["Failed to remove previous Discord link. Please try again.", "Die vorherige Discord-Verknüpfung konnte nicht entfernt werden. Bitte versuche es erneut.", "No se pudo eliminar el vínculo de Discord anterior. Por favor inténtalo de nuevo."]),
+    ("unlink.done", ["Discord `{}` has been removed if it existed", "Discord `{}` wurde entfernt, falls vorhanden", "Discord `{}` ha sido eliminado si existía"]),
+    //Id
+    ("id.found", ["The player id of {} is `{}`", "Die Spieler-ID von {} ist `{}`", "El ID de jugador de {} es `{}`"]),
+    ("id.none", ["No players matched the name `{}`!\nDid you spell it right? (Caps don't affect search)", "Kein Spieler heißt `{}`!\nRichtig geschrieben? (Groß-/Kleinschreibung ist egal)", "¡Ningún jugador coincide con el nombre `{}`!\n¿Lo escribiste bien? (Las mayúsculas no afectan la búsqueda)"]),
+    ("id.error", ["Error searching for and fetching player data from API: {}", "Fehler beim Suchen und Abrufen der Spielerdaten von der API: {}", "Error al buscar y obtener los datos del jugador de la API: {}"]),
+    //Setchannel
+    ("setchannel.done", ["Announcements (like the weekly biggest climbers) will be posted in this channel!", "Ankündigungen (wie die größten Aufsteiger der Woche) werden in diesem Kanal gepostet!", "¡Los anuncios (como los mayores escaladores de la semana) se publicarán en este canal!"]),
+    ("setchannel.failed", ["Failed to set the announcement channel. Please try again.", "Der Ankündigungskanal konnte nicht gesetzt werden. Bitte versuche es erneut.", "No se pudo establecer el canal de anuncios. Por favor inténtalo de nuevo."]),
+    ("setchannel.server_only", ["Announcement channels can only be set inside of a server.", "Ankündigungskanäle können nur in einem Server gesetzt werden.", "Los canales de anuncios solo se pueden establecer dentro de un servidor."]),
+    //Language
+    ("language.set", ["Language set to {}!", "Sprache auf {} gesetzt!", "¡Idioma cambiado a {}!"]),
+    ("language.reset", ["Language has been reset!", "Sprache wurde zurückgesetzt!", "¡El idioma se ha restablecido!"]),
+    ("language.show", ["Language: {}", "Sprache: {}", "Idioma: {}"]),
+    ("language.invalid", ["Invalid language. Use `en`, `de` or `es`.", "Ungültige Sprache. Verwende `en`, `de` oder `es`.", "Idioma no válido. Usa `en`, `de` o `es`."]),
+    ("language.server_only", ["Server languages can only be set inside of a server.", "Server-Sprachen können nur in einem Server gesetzt werden.", "Los idiomas del servidor solo se pueden establecer dentro de un servidor."]),
+    ("language.failed", ["Failed to save the language. Please try again.", "Die Sprache konnte nicht gespeichert werden. Bitte versuche es erneut.", "No se pudo guardar el idioma. Por favor inténtalo de nuevo."]),
+    //Help
+    ("help.title", ["ScoreSaber Stats - Help", "ScoreSaber Stats - Hilfe", "ScoreSaber Stats - Ayuda"]),
+    ("help.help", ["Provides list of commands", "Zeigt die Liste der Befehle", "Muestra la lista de comandos"]),
+    ("help.stats", ["Provides player statistics", "Zeigt die Statistiken eines Spielers", "Muestra las estadísticas de un jugador"]),
+    ("help.track", ["Checks a player every few seconds and posts a recap after each play session", "Prüft einen Spieler alle paar Sekunden und postet nach jeder Spielsession eine Zusammenfassung", "Revisa a un jugador cada pocos segundos y publica un resumen después de cada sesión de juego"]),
+    ("help.untrack", ["Stops tracking", "Stoppt das Tracking", "Detiene el seguimiento"]),
+    ("help.link", ["Links player_id to messenger's discord", "Verknüpft player_id mit deinem Discord", "Vincula player_id con tu discord"]),
+    ("help.unlink", ["Deletes link to messenger's discord", "Löscht die Verknüpfung mit deinem Discord", "Elimina el vínculo con tu discord"]),
+    ("help.id", ["Find a player_id by name", "Findet eine player_id anhand des Namens", "Busca un player_id por nombre"]),
+    ("help.leaderboard", ["Ranks linked players in this server by any stat or weeklyGain", "Rangliste der verknüpften Spieler dieses Servers nach einer Statistik oder weeklyGain", "Clasifica a los jugadores vinculados de este servidor por cualquier estadística o weeklyGain"]),
+    ("help.rival", ["Manage rivals, overtakes are announced", "Rivalen verwalten, Überholungen werden angekündigt", "Gestiona rivales, los adelantamientos se anuncian"]),
+    ("help.milestone", ["Manage server milestones for any stat", "Server-Meilensteine für jede Statistik verwalten", "Gestiona los hitos del servidor para cualquier estadística"]),
+    ("help.goal", ["Track progress towards a goal for any stat", "Fortschritt zu einem Ziel für jede Statistik verfolgen", "Sigue el progreso hacia una meta para cualquier estadística"]),
+    ("help.stat_names", ["STAT NAMES", "STATISTIK-NAMEN", "NOMBRES DE ESTADÍSTICAS"]),
+    ("help.theme", ["Pick embed colors (hex) and layout (compact, detailed, grid)", "Embed-Farben (Hex) und Layout (compact, detailed, grid) wählen", "Elige los colores (hex) y el diseño (compact, detailed, grid) de los embeds"]),
+    ("help.fields", ["Pick the stats shown in `!stats` and that trigger `!track` updates", "Statistiken für `!stats` und `!track`-Updates wählen", "Elige las estadísticas que muestra `!stats` y que activan `!track`"]),
+    ("help.card", ["Shareable profile card image", "Teilbares Profilkarten-Bild", "Imagen de tarjeta de perfil para compartir"]),
+    ("help.country", ["Top players of a country on ScoreSaber", "Top-Spieler eines Landes auf ScoreSaber", "Mejores jugadores de un país en ScoreSaber"]),
+    ("help.country-stats", ["Statistics of the tracked players of a country", "Statistiken der getrackten Spieler eines Landes", "Estadísticas de los jugadores seguidos de un país"]),
+    ("help.neighbours", ["The 5 players ranked just above and below, with the pp gap to each", "Die 5 Spieler direkt über und unter dir, mit dem pp-Abstand zu jedem", "Los 5 jugadores justo por encima y por debajo, con la diferencia de pp a cada uno"]),
+    ("help.reach", ["PP needed to reach a global or country rank and how many plays it takes", "Benötigte pp für einen globalen oder Landesrang und wie viele Plays es braucht", "PP necesarios para alcanzar un rango global o del país y cuántas partidas hacen falta"]),
+    ("help.recommend", ["Ranked maps with the biggest expected pp gain, with expected accuracy, pp and a playlist", "Gerankte Maps mit dem größten erwarteten pp-Gewinn, mit erwarteter Genauigkeit, pp und Playlist", "Mapas rankeados con la mayor ganancia de pp esperada, con precisión, pp esperados y una playlist"]),
+    ("help.snipe", ["Ranked maps where the target beats you, sorted by the pp you gain by matching them, with a playlist", "Gerankte Maps, auf denen das Ziel dich schlägt, sortiert nach dem pp-Gewinn beim Gleichziehen, mit Playlist", "Mapas rankeados donde el objetivo te supera, ordenados por los pp que ganas al igualarlo, con una playlist"]),
+    ("help.unplayed", ["Ranked maps in a star range you haven't played, with a playlist of all of them", "Gerankte Maps in einem Sternebereich, die du noch nicht gespielt hast, mit einer Playlist aller Maps", "Mapas rankeados en un rango de estrellas que no has jugado, con una playlist de todos ellos"]),
+    ("help.acc-profile", ["Accuracy by star rating (average, median, best) with the ranges you under-perform in", "Genauigkeit nach Sternen (Durchschnitt, Median, Bestwert) mit den Bereichen, in denen du schwächelst", "Precisión por estrellas (media, mediana, mejor) con los rangos donde rindes por debajo"]),
+    ("help.mapboard", ["How the linked players of this server scored on a map, by score or accuracy", "Wie die verknüpften Spieler dieses Servers auf einer Map abgeschnitten haben, nach Score oder Genauigkeit", "Cómo puntuaron los jugadores vinculados de este servidor en un mapa, por puntuación o precisión"]),
+    ("help.streak", ["Current and longest streak of days with plays", "Aktuelle und längste Serie an Tagen mit Plays", "Racha actual y más larga de días con partidas"]),
+    ("help.calendar", ["Heatmap image of the plays per day over the last year", "Heatmap-Bild der Plays pro Tag im letzten Jahr", "Imagen de mapa de calor de las partidas por día del último año"]),
+    ("help.remind", ["Opt into a reminder in this channel after some days without a new score", "Erinnerung in diesem Kanal nach einigen Tagen ohne neuen Score aktivieren", "Activa un recordatorio en este canal tras algunos días sin una puntuación nueva"]),
+    ("help.language", ["Pick the language and number format of the bot (en, de, es)", "Sprache und Zahlenformat des Bots wählen (en, de, es)", "Elige el idioma y el formato de números del bot (en, de, es)"]),
+    ("help.setchannel", ["Posts announcements (weekly biggest climbers, rival overtakes, milestones, goals, newly ranked maps) in this channel", "Postet Ankündigungen (größte Aufsteiger der Woche, Rivalen-Überholungen, Meilensteine, Ziele, neu gerankte Maps) in diesem Kanal", "Publica anuncios (mayores escaladores de la semana, adelantamientos de rivales, hitos, metas, mapas recién rankeados) en este canal"]),
+    //Country
+    ("country.invalid", ["Invalid country or page. Use a two letter country code.", "Ungültiges Land oder ungültige Seite. Verwende einen zweistelligen Ländercode.", "País o página no válidos. Usa un código de país de dos letras."]),
+    ("country.fetch_failed", ["Failed to fetch the country leaderboard. Please try again.", "Die Länderrangliste konnte nicht abgerufen werden. Bitte versuche es erneut.", "No se pudo obtener la clasificación del país. Por favor inténtalo de nuevo."]),
+    ("country.empty", ["No players found for `{}` on page {}.", "Keine Spieler für `{}` auf Seite {} gefunden.", "No se encontraron jugadores de `{}` en la página {}."]),
+    ("country.title", ["{} Top Players - {}", "{} Top-Spieler - {}", "{} Mejores jugadores - {}"]),
+    ("country.footer", ["Page {}/{} ({} players)", "Seite {}/{} ({} Spieler)", "Página {}/{} ({} jugadores)"]),
+    ("country_stats.invalid", ["Invalid country. Use a two letter country code.", "Ungültiges Land. Verwende einen zweistelligen Ländercode.", "País no válido. Usa un código de país de dos letras."]),
+    ("country_stats.none", ["No tracked players from `{}` yet!", "Noch keine getrackten Spieler aus `{}`!", "¡Todavía no hay jugadores seguidos de `{}`!"]),
+    ("country_stats.failed", ["Failed to load the country stats. Please try again.", "Die Landesstatistiken konnten nicht geladen werden. Bitte versuche es erneut.", "No se pudieron cargar las estadísticas del país. Por favor inténtalo de nuevo."]),
+    ("country_stats.title", ["{} Tracked Players - {}", "{} Getrackte Spieler - {}", "{} Jugadores seguidos - {}"]),
+    ("country_stats.tracked", ["Tracked", "Getrackt", "Seguidos"]),
+    ("country_stats.linked", ["Linked", "Verknüpft", "Vinculados"]),
+    ("country_stats.ranked", ["Ranked", "Gerankt", "Clasificados"]),
+    ("country_stats.average_pp", ["Average PP", "Durchschnittliche PP", "PP medios"]),
+    ("country_stats.average_acc", ["Average Accuracy", "Durchschnittliche Genauigkeit", "Precisión media"]),
+    ("country_stats.best", ["Best Tracked Players", "Beste getrackte Spieler", "Mejores jugadores seguidos"]),
+    //Neighbours
+    ("neighbours.unranked", ["**{}** is not ranked right now.", "**{}** ist gerade nicht gerankt.", "**{}** no está clasificado ahora mismo."]),
+    ("neighbours.title_country", ["{} Country Neighbours of {}", "{} Landesnachbarn von {}", "{} Vecinos del país de {}"]),
+    ("neighbours.title_global", ["Global Neighbours of {}", "Globale Nachbarn von {}", "Vecinos globales de {}"]),
+    //Reach
+    ("reach.one_play", ["**1** play of {}", "**1** Play mit {}", "**1** partida de {}"]),
+    ("reach.plays", ["**{}** plays of {}", "**{}** Plays mit {}", "**{}** partidas de {}"]),
+    ("reach.too_many", ["More than {} plays of {} (the next one adds {}pp)", "Mehr als {} Plays mit {} (der nächste bringt {}pp)", "Más de {} partidas de {} (la siguiente suma {}pp)"]),
+    ("reach.invalid", ["Invalid rank or pp. Use positive numbers.", "Ungültiger Rang oder pp. Verwende positive Zahlen.", "Rango o pp no válidos. Usa números positivos."]),
+    ("reach.already", ["**{}** is already #{}!", "**{}** ist bereits #{}!", "¡**{}** ya es #{}!"]),
+    ("reach.no_holder", ["Failed to find who is #{} right now. Please try again.", "Es konnte nicht gefunden werden, wer gerade #{} ist. Bitte versuche es erneut.", "No se pudo encontrar quién es #{} ahora mismo. Por favor inténtalo de nuevo."]),
+    ("reach.holder", ["Rank Holder", "Aktueller Inhaber", "Titular del rango"]),
+    ("reach.needed", ["PP Needed", "Benötigte PP", "PP necesarios"]),
+    ("reach.estimates", ["Estimated New Plays", "Geschätzte neue Plays", "Partidas nuevas estimadas"]),
+    ("reach.no_scores", ["No ranked scores to estimate with yet!", "Noch keine gerankten Scores für eine Schätzung!", "¡Todavía no hay puntuaciones ranked para estimar!"]),
+    ("reach.footer", ["Estimates assume new maps and use the 0.965 pp weighting of your top scores.", "Schätzungen gehen von neuen Maps aus und nutzen die 0,965-pp-Gewichtung deiner Top-Scores.", "Las estimaciones suponen mapas nuevos y usan la ponderación de pp 0,965 de tus mejores puntuaciones."]),
+    //Maps
+    ("maps.line", ["[{}]({}) by {} [{}] **{}★**", "[{}]({}) von {} [{}] **{}★**", "[{}]({}) de {} [{}] **{}★**"]),
+    ("maps.continued", ["{} (continued)", "{} (Fortsetzung)", "{} (continuación)"]),
+    ("maps.was", ["{} (was {}★)", "{} (vorher {}★)", "{} (antes {}★)"]),
+    ("maps.ranked", ["Newly Ranked Maps", "Neu gerankte Maps", "Mapas recién clasificados"]),
+    ("maps.rerated", ["Re-rated Maps", "Neu bewertete Maps", "Mapas revalorados"]),
+    ("maps.unranked", ["Unranked Maps", "Entrankte Maps", "Mapas desclasificados"]),
+    //Recommend
+    ("recommend.line", ["**{}.** {}\nExpected **{}** → **{}pp** (`+{}pp`)", "**{}.** {}\nErwartet **{}** → **{}pp** (`+{}pp`)", "**{}.** {}\nEsperado **{}** → **{}pp** (`+{}pp`)"]),
+    ("recommend.improves", [" - improves your {}pp", " - verbessert deine {}pp", " - mejora tus {}pp"]),
+    ("recommend.invalid", ["Invalid options.", "Ungültige Optionen.", "Opciones no válidas."]),
+    ("recommend.no_scores", ["**{}** has no ranked scores to base recommendations on yet!", "**{}** hat noch keine gerankten Scores für Empfehlungen!", "¡**{}** todavía no tiene puntuaciones ranked para basar recomendaciones!"]),
+    ("recommend.no_maps", ["No ranked maps found in that star range. The map catalogue might still be syncing, please try again later.", "Keine gerankten Maps in diesem Sternebereich gefunden. Der Map-Katalog wird eventuell noch synchronisiert, bitte versuche es später erneut.", "No se encontraron mapas ranked en ese rango de estrellas. El catálogo de mapas podría estar sincronizándose, por favor inténtalo más tarde."]),
+    ("recommend.title", ["Recommended Maps for {} ({}★ - {}★)", "Empfohlene Maps für {} ({}★ - {}★)", "Mapas recomendados para {} ({}★ - {}★)"]),
+    ("recommend.nothing", ["Nothing to gain at your expected accuracy in this range!", "Mit deiner erwarteten Genauigkeit gibt es in diesem Bereich nichts zu gewinnen!", "¡No hay nada que ganar con tu precisión esperada en este rango!"]),
+    ("recommend.footer", ["Expected accuracy is fitted to your top scores by star rating, pp comes from the ScoreSaber pp curve.", "Die erwartete Genauigkeit wird nach Sternen an deine Top-Scores angepasst, pp kommen aus der ScoreSaber-pp-Kurve.", "La precisión esperada se ajusta a tus mejores puntuaciones por estrellas, los pp vienen de la curva de pp de ScoreSaber."]),
+    ("recommend.playlist", ["Recommended for {}", "Empfohlen für {}", "Recomendado para {}"]),
+    ("recommend.playlist_description", ["Maps with the biggest expected pp gain", "Maps mit dem größten erwarteten pp-Gewinn", "Mapas con la mayor ganancia de pp esperada"]),
+    ("unplayed.invalid", ["Invalid star range.", "Ungültiger Sternebereich.", "Rango de estrellas no válido."]),
+    ("unplayed.failed", ["Failed to load the maps. Please try again.", "Die Maps konnten nicht geladen werden. Bitte versuche es erneut.", "No se pudieron cargar los mapas. Por favor inténtalo de nuevo."]),
+    ("unplayed.title", ["Unplayed Maps for {} ({}★ - {}★)", "Ungespielte Maps für {} ({}★ - {}★)", "Mapas sin jugar para {} ({}★ - {}★)"]),
+    ("unplayed.all_played", ["Every ranked map in this range is played!", "Jede gerankte Map in diesem Bereich ist gespielt!", "¡Todos los mapas ranked de este rango están jugados!"]),
+    ("unplayed.footer", ["{} unplayed ranked maps, all of them are in the playlist.", "{} ungespielte gerankte Maps, alle sind in der Playlist.", "{} mapas ranked sin jugar, todos están en la playlist."]),
+    ("unplayed.playlist_description", ["Ranked maps in this star range that are not played yet", "Gerankte Maps in diesem Sternebereich, die noch nicht gespielt sind", "Mapas ranked en este rango de estrellas que aún no se han jugado"]),
+    //Snipe
+    ("snipe.yours", ["yours {}", "deine {}", "tuya {}"]),
+    ("snipe.unplayed", ["unplayed", "ungespielt", "sin jugar"]),
+    ("snipe.line", ["**{}.** [{}]({}) by {} [{}] {}★ - {} vs {} (`+{}pp`)", "**{}.** [{}]({}) von {} [{}] {}★ - {} vs {} (`+{}pp`)", "**{}.** [{}]({}) de {} [{}] {}★ - {} vs {} (`+{}pp`)"]),
+    ("snipe.yourself", ["You can't snipe yourself!", "Du kannst dich nicht selbst snipen!", "¡No puedes hacerte snipe a ti mismo!"]),
+    ("snipe.title", ["{} → {}: Snipe List", "{} → {}: Snipe-Liste", "{} → {}: Lista de snipes"]),
+    ("snipe.none", ["**{}** doesn't beat you on any ranked map!", "**{}** schlägt dich auf keiner gerankten Map!", "¡**{}** no te gana en ningún mapa ranked!"]),
+    ("snipe.footer", ["{} maps they beat you on, {} you haven't played. Gains match their accuracy.", "{} Maps, auf denen du geschlagen wirst, {} davon ungespielt. Gewinne entsprechen ihrer Genauigkeit.", "{} mapas en los que te gana, {} que no has jugado. Las ganancias igualan su precisión."]),
+    ("snipe.playlist", ["Snipe {}", "Snipe {}", "Snipe {}"]),
+    ("snipe.playlist_description", ["Maps where {} beats {}", "Maps, auf denen {} {} schlägt", "Mapas donde {} le gana a {}"]),
+    //Accuracy profile
+    ("acc_profile.header", ["Stars|Plays|Avg|Median|Best", "Sterne|Plays|Schnitt|Median|Best", "Estrellas|Partidas|Media|Mediana|Mejor"]),
+    ("acc_profile.none", ["**{}** has no ranked scores yet!", "**{}** hat noch keine gerankten Scores!", "¡**{}** todavía no tiene puntuaciones ranked!"]),
+    ("acc_profile.below", ["**{}★ - {}★**: {} below your average", "**{}★ - {}★**: {} unter deinem Durchschnitt", "**{}★ - {}★**: {} por debajo de tu media"]),
+    ("acc_profile.title", ["Accuracy Profile of {}", "Genauigkeitsprofil von {}", "Perfil de precisión de {}"]),
+    ("acc_profile.under", ["Under-performing", "Schwächen", "Por debajo"]),
+    ("acc_profile.consistent", ["None, nice and consistent!", "Keine, schön konstant!", "¡Ninguno, muy constante!"]),
+    ("acc_profile.footer", ["Marked (<) buckets average more than {} points under your ranked accuracy with at least {} plays.", "Markierte (<) Bereiche liegen im Schnitt mehr als {} Punkte unter deiner Ranked-Genauigkeit, mit mindestens {} Plays.", "Los rangos marcados (<) promedian más de {} puntos por debajo de tu precisión ranked con al menos {} partidas."]),
+    //Mapboard
+    ("mapboard.server_only", ["Mapboards can only be used inside of a server.", "Mapboards können nur in einem Server verwendet werden.", "Los mapboards solo se pueden usar dentro de un servidor."]),
+    ("mapboard.members_failed", ["Failed to load the members of this server. Please try again.", "Die Mitglieder dieses Servers konnten nicht geladen werden. Bitte versuche es erneut.", "No se pudieron cargar los miembros de este servidor. Por favor inténtalo de nuevo."]),
+    ("mapboard.not_found", ["No map found for `{}`. Use a leaderboard id or part of the song name.", "Keine Map für `{}` gefunden. Verwende eine Leaderboard-ID oder einen Teil des Songnamens.", "No se encontró ningún mapa para `{}`. Usa un id de leaderboard o parte del nombre de la canción."]),
+    ("mapboard.map_failed", ["Failed to load the map. Please try again.", "Die Map konnte nicht geladen werden. Bitte versuche es erneut.", "No se pudo cargar el mapa. Por favor inténtalo de nuevo."]),
+    ("mapboard.no_linked", ["No linked players in this server yet!", "Noch keine verknüpften Spieler in diesem Server!", "¡Todavía no hay jugadores vinculados en este servidor!"]),
+    ("mapboard.failed", ["Failed to load the mapboard. Please try again.", "Das Mapboard konnte nicht geladen werden. Bitte versuche es erneut.", "No se pudo cargar el mapboard. Por favor inténtalo de nuevo."]),
+    ("mapboard.title", ["{} [{}]{} - Server Mapboard", "{} [{}]{} - Server-Mapboard", "{} [{}]{} - Mapboard del servidor"]),
+    ("mapboard.nobody", ["Nobody in this server has played this map yet!", "Niemand in diesem Server hat diese Map bisher gespielt!", "¡Nadie en este servidor ha jugado este mapa todavía!"]),
+    ("mapboard.by_score", ["score", "Score", "puntuación"]),
+    ("mapboard.by_acc", ["accuracy", "Genauigkeit", "precisión"]),
+    ("mapboard.footer", ["Ranked by {}, {} of {} linked players played it.", "Sortiert nach {}, {} von {} verknüpften Spielern haben sie gespielt.", "Ordenado por {}, {} de {} jugadores vinculados lo jugaron."]),
+    ("mapboard.others", [" Other difficulties: {}", " Andere Schwierigkeiten: {}", " Otras dificultades: {}"]),
+    ("mapboard.sync_failed", [" Failed to refresh the scores of {} players.", " Die Scores von {} Spielern konnten nicht aktualisiert werden.", " No se pudieron actualizar las puntuaciones de {} jugadores."]),
+    //Session recap
+    ("session.title", ["Session Recap - {}", "Session-Rückblick - {}", "Resumen de la sesión - {}"]),
+    ("session.plays", ["Plays", "Spiele", "Partidas"]),
+    ("session.pbs", ["PBs", "PBs", "Récords"]),
+    ("session.length", ["Session Length", "Session-Dauer", "Duración"]),
+    ("session.pp", ["PP Gained", "Gewonnene PP", "PP ganados"]),
+    ("session.rank", ["Rank", "Rang", "Rango"]),
+    ("session.best", ["Best Accuracy", "Beste Genauigkeit", "Mejor precisión"]),
+    ("session.worst", ["Worst Accuracy", "Schlechteste Genauigkeit", "Peor precisión"]),
+    ("session.score", ["{} on {} [{}]", "{} auf {} [{}]", "{} en {} [{}]"]),
+    ("session.footer", ["Session ended after {} minutes without a new score.", "Die Session endete nach {} Minuten ohne neuen Score.", "La sesión terminó tras {} minutos sin una nueva puntuación."]),
+    //Activity
+    ("activity.failed", ["Failed to load the activity. Please try again.", "Die Aktivität konnte nicht geladen werden. Bitte versuche es erneut.", "No se pudo cargar la actividad. Por favor inténtalo de nuevo."]),
+    ("streak.title", ["Play Streak of {}", "Spielserie von {}", "Racha de juego de {}"]),
+    ("streak.current", ["Current Streak", "Aktuelle Serie", "Racha actual"]),
+    ("streak.longest", ["Longest Streak", "Längste Serie", "Racha más larga"]),
+    ("streak.active", ["Active Days (30d)", "Aktive Tage (30T)", "Días activos (30d)"]),
+    ("streak.last", ["Last Played", "Zuletzt gespielt", "Última partida"]),
+    ("streak.never", ["Never", "Nie", "Nunca"]),
+    ("streak.day", [" day", " Tag", " día"]),
+    ("streak.days", [" days", " Tage", " días"]),
+    ("streak.footer", ["Days count in UTC. Plays come from tracked stats and the scores feed (improved scores replace older ones).", "Tage zählen in UTC. Spiele stammen aus den getrackten Stats und den Scores (verbesserte Scores ersetzen ältere).", "Los días cuentan en UTC. Las partidas vienen de las estadísticas registradas y de las puntuaciones (las mejoradas reemplazan a las anteriores)."]),
+    ("calendar.title", ["{} - {} plays in the last year", "{} - {} Spiele im letzten Jahr", "{} - {} partidas en 12 meses"]), //Calendar texts are drawn with the pixel font (ASCII only)
+    ("calendar.less", ["Less", "Weniger", "Menos"]),
+    ("calendar.more", ["More", "Mehr", "Mas"]),
+    ("calendar.months", ["Jan|Feb|Mar|Apr|May|Jun|Jul|Aug|Sep|Oct|Nov|Dec", "Jan|Feb|Mrz|Apr|Mai|Jun|Jul|Aug|Sep|Okt|Nov|Dez", "Ene|Feb|Mar|Abr|May|Jun|Jul|Ago|Sep|Oct|Nov|Dic"]),
+    ("calendar.failed", ["Failed to render the calendar. Please try again.", "Der Kalender konnte nicht erstellt werden. Bitte versuche es erneut.", "No se pudo generar el calendario. Por favor inténtalo de nuevo."]),
+    ("remind.link_first", ["Please link your account first!", "Bitte verknüpfe zuerst dein Konto!", "¡Por favor vincula tu cuenta primero!"]),
+    ("remind.invalid_days", ["Invalid amount of days (1-{}).", "Ungültige Anzahl an Tagen (1-{}).", "Cantidad de días no válida (1-{})."]),
+    ("remind.on", ["You will be reminded in this channel after **{}** days without a new score.", "Du wirst in diesem Kanal nach **{}** Tagen ohne neuen Score erinnert.", "Se te recordará en este canal tras **{}** días sin una nueva puntuación."]),
+    ("remind.set_failed", ["Failed to set the reminder. Please try again.", "Die Erinnerung konnte nicht gesetzt werden. Bitte versuche es erneut.", "No se pudo activar el recordatorio. Por favor inténtalo de nuevo."]),
+    ("remind.off", ["You will no longer get inactivity reminders.", "Du bekommst keine Inaktivitäts-Erinnerungen mehr.", "Ya no recibirás recordatorios de inactividad."]),
+    ("remind.not_on", ["You don't have reminders turned on!", "Du hast keine Erinnerungen aktiviert!", "¡No tienes los recordatorios activados!"]),
+    ("remind.remove_failed", ["Failed to remove the reminder. Please try again.", "Die Erinnerung konnte nicht entfernt werden. Bitte versuche es erneut.", "No se pudo quitar el recordatorio. Por favor inténtalo de nuevo."]),
+    ("remind.never_played", ["You haven't set a score on ScoreSaber yet, time to play some Beat Saber!", "Du hast noch keinen Score auf ScoreSaber, Zeit für etwas Beat Saber!", "¡Todavía no tienes puntuaciones en ScoreSaber, es hora de jugar Beat Saber!"]),
+    ("remind.inactive", ["You haven't set a new score in **{}** days, time to play some Beat Saber!", "Du hast seit **{}** Tagen keinen neuen Score gesetzt, Zeit für etwas Beat Saber!", "No has conseguido una nueva puntuación en **{}** días, ¡es hora de jugar Beat Saber!"]),
+    //Leaderboard
+    ("leaderboard.guild_only", ["Leaderboards can only be used inside of a server.", "Bestenlisten gibt es nur auf einem Server.", "Las clasificaciones solo se pueden usar dentro de un servidor."]),
+    ("leaderboard.invalid_sort", ["Invalid sort.", "Ungültige Sortierung.", "Orden no válido."]),
+    ("leaderboard.members_failed", ["Failed to load the members of this server. Please try again.", "Die Mitglieder dieses Servers konnten nicht geladen werden. Bitte versuche es erneut.", "No se pudieron cargar los miembros de este servidor. Por favor inténtalo de nuevo."]),
+    ("leaderboard.failed", ["Failed to load the leaderboard. Please try again.", "Die Bestenliste konnte nicht geladen werden. Bitte versuche es erneut.", "No se pudo cargar la clasificación. Por favor inténtalo de nuevo."]),
+    ("leaderboard.empty", ["No linked players in this server yet!", "Auf diesem Server gibt es noch keine verknüpften Spieler!", "¡Todavía no hay jugadores vinculados en este servidor!"]),
+    ("leaderboard.weekly_gain", ["Weekly PP Gain", "Wöchentlicher PP-Gewinn", "PP ganados esta semana"]),
+    ("leaderboard.title", ["Server Leaderboard - {}", "Server-Bestenliste - {}", "Clasificación del servidor - {}"]),
+    ("leaderboard.refreshing", ["Refreshing {} outdated player(s) in the background.", "{} veraltete Spieler werden im Hintergrund aktualisiert.", "Actualizando {} jugador(es) desactualizado(s) en segundo plano."]),
+    ("climbers.title", ["Weekly Biggest Climbers", "Größte Aufsteiger der Woche", "Mayores escaladores de la semana"]),
+    ("climbers.field", ["Biggest {} Gain", "Größter Zuwachs: {}", "Mayor ganancia: {}"]),
+    ("climbers.nobody", ["Nobody this week!", "Diese Woche niemand!", "¡Nadie esta semana!"]),
+    //Goals
+    ("goal.title", ["Goals", "Ziele", "Objetivos"]),
+    ("goal.target", ["Target: **{}** (now {})", "Ziel: **{}** (jetzt {})", "Objetivo: **{}** (ahora {})"]),
+    ("goal.reached_on", ["Reached on {}!", "Erreicht am {}!", "¡Alcanzado el {}!"]),
+    ("goal.projected", ["Projected: {}", "Prognose: {}", "Previsión: {}"]),
+    ("goal.behind", ["behind schedule", "hinter dem Zeitplan", "con retraso"]),
+    ("goal.on_track", ["on track", "im Zeitplan", "a tiempo"]),
+    ("goal.no_projection", ["Projected: not enough progress yet", "Prognose: noch nicht genug Fortschritt", "Previsión: todavía no hay suficiente progreso"]),
+    ("goal.deadline", ["Deadline: {}", "Frist: {}", "Fecha límite: {}"]),
+    ("goal.reached_title", ["Goal Reached!", "Ziel erreicht!", "¡Objetivo alcanzado!"]),
+    ("goal.reached", ["Congratulations **{}**, you reached your goal of **{}** (set on {})!", "Glückwunsch **{}**, du hast dein Ziel von **{}** erreicht (gesetzt am {})!", "¡Felicidades **{}**, alcanzaste tu objetivo de **{}** (fijado el {})!"]),
+    ("goal.invalid", ["Invalid goal.", "Ungültiges Ziel.", "Objetivo no válido."]),
+    ("goal.set", ["Goal set: **{}** (now {})", "Ziel gesetzt: **{}** (jetzt {})", "Objetivo fijado: **{}** (ahora {})"]),
+    ("goal.already_reached", ["You already reached that goal! Try a bigger one.", "Dieses Ziel hast du schon erreicht! Versuch ein größeres.", "¡Ya alcanzaste ese objetivo! Prueba uno más grande."]),
+    ("goal.set_failed", ["Failed to set goal. Please try again.", "Das Ziel konnte nicht gesetzt werden. Bitte versuche es erneut.", "No se pudo fijar el objetivo. Por favor inténtalo de nuevo."]),
+    ("goal.removed", ["Your `{}` goal has been removed.", "Dein `{}`-Ziel wurde entfernt.", "Tu objetivo de `{}` ha sido eliminado."]),
+    ("goal.not_found", ["You don't have a goal for that!", "Dafür hast du kein Ziel!", "¡No tienes un objetivo para eso!"]),
+    ("goal.remove_failed", ["Failed to remove goal. Please try again.", "Das Ziel konnte nicht entfernt werden. Bitte versuche es erneut.", "No se pudo eliminar el objetivo. Por favor inténtalo de nuevo."]),
+    ("goal.none", ["You have no goals yet!", "Du hast noch keine Ziele!", "¡Todavía no tienes objetivos!"]),
+    //Milestones
+    ("milestone.top", ["Top {} ({})", "Top {} ({})", "Top {} ({})"]),
+    ("milestone.title", ["Milestone Reached!", "Meilenstein erreicht!", "¡Hito alcanzado!"]),
+    ("milestone.reached", ["**{}** just reached:", "**{}** hat gerade erreicht:", "**{}** acaba de alcanzar:"]),
+    ("milestone.guild_only", ["Milestones can only be configured inside of a server.", "Meilensteine können nur auf einem Server eingestellt werden.", "Los hitos solo se pueden configurar dentro de un servidor."]),
+    ("milestone.defaults", ["Default milestones:", "Standard-Meilensteine:", "Hitos predeterminados:"]),
+    ("milestone.server", ["Server milestones:", "Server-Meilensteine:", "Hitos del servidor:"]),
+    ("milestone.none", ["None yet!", "Noch keine!", "¡Ninguno todavía!"]),
+    ("milestone.invalid", ["Invalid milestone. Use a positive number and one of: `{}`", "Ungültiger Meilenstein. Verwende eine positive Zahl und eines von: `{}`", "Hito no válido. Usa un número positivo y uno de: `{}`"]),
+    ("milestone.added", ["Milestone **{}** added!", "Meilenstein **{}** hinzugefügt!", "¡Hito **{}** añadido!"]),
+    ("milestone.removed", ["Milestone **{}** removed!", "Meilenstein **{}** entfernt!", "¡Hito **{}** eliminado!"]),
+    ("milestone.not_found", ["That milestone does not exist!", "Diesen Meilenstein gibt es nicht!", "¡Ese hito no existe!"]),
+    ("milestone.failed", ["Failed to update milestones. Please try again.", "Die Meilensteine konnten nicht aktualisiert werden. Bitte versuche es erneut.", "No se pudieron actualizar los hitos. Por favor inténtalo de nuevo."]),
+    //Profile card
+    ("card.rank_history", ["RANK HISTORY", "RANGVERLAUF", "HISTORIAL DE RANGO"]), //Card texts are drawn with the pixel font (ASCII only)
+    ("card.not_enough_data", ["Not enough data", "Zu wenig Daten", "Datos insuficientes"]),
+    ("card.acc", ["{} acc", "{} Genauigkeit", "{} precision"]),
+    ("card.top_play", ["Top play: {} [{}] {}pp", "Bester Score: {} [{}] {}pp", "Mejor jugada: {} [{}] {}pp"]),
+    ("card.no_top_play", ["Top play: none", "Bester Score: keiner", "Mejor jugada: ninguna"]),
+    ("card.failed", ["Failed to render the profile card. Please try again.", "Die Profilkarte konnte nicht erstellt werden. Bitte versuche es erneut.", "No se pudo generar la tarjeta de perfil. Por favor inténtalo de nuevo."]),
+    //Theme
+    ("theme.guild_only", ["Server themes can only be set inside of a server.", "Server-Designs können nur auf einem Server gesetzt werden.", "Los temas del servidor solo se pueden configurar dentro de un servidor."]),
+    ("theme.load_failed", ["Failed to load the theme. Please try again.", "Das Design konnte nicht geladen werden. Bitte versuche es erneut.", "No se pudo cargar el tema. Por favor inténtalo de nuevo."]),
+    ("theme.color_set", ["Theme color set to `{}`!", "Designfarbe auf `{}` gesetzt!", "¡Color del tema cambiado a `{}`!"]),
+    ("theme.accent_set", ["Theme accent set to `{}`!", "Akzentfarbe auf `{}` gesetzt!", "¡Acento del tema cambiado a `{}`!"]),
+    ("theme.invalid_color", ["Invalid color. Use a hex color like `#550000`.", "Ungültige Farbe. Verwende eine Hex-Farbe wie `#550000`.", "Color no válido. Usa un color hexadecimal como `#550000`."]),
+    ("theme.layout_set", ["Theme layout set to `{}`!", "Layout auf `{}` gesetzt!", "¡Diseño del tema cambiado a `{}`!"]),
+    ("theme.invalid_layout", ["Invalid layout. Use `compact`, `detailed` or `grid`.", "Ungültiges Layout. Verwende `compact`, `detailed` oder `grid`.", "Diseño no válido. Usa `compact`, `detailed` o `grid`."]),
+    ("theme.reset", ["Theme has been reset!", "Das Design wurde zurückgesetzt!", "¡El tema se ha restablecido!"]),
+    ("theme.show", ["Color: `{}`\nAccent: `{}`\nLayout: `{}`", "Farbe: `{}`\nAkzent: `{}`\nLayout: `{}`", "Color: `{}`\nAcento: `{}`\nDiseño: `{}`"]),
+    ("theme.default", ["default", "Standard", "predeterminado"]),
+    ("theme.save_failed", ["Failed to save the theme. Please try again.", "Das Design konnte nicht gespeichert werden. Bitte versuche es erneut.", "No se pudo guardar el tema. Por favor inténtalo de nuevo."]),
+    //Fields
+    ("fields.invalid", ["Invalid stats: `{}`. Pick from: `{}`", "Ungültige Stats: `{}`. Wähle aus: `{}`", "Estadísticas no válidas: `{}`. Elige entre: `{}`"]),
+    ("fields.set", ["Your stats embed will show: `{}`", "Dein Stats-Embed zeigt: `{}`", "Tu embed de estadísticas mostrará: `{}`"]),
+    ("fields.reset", ["Your stats embed will show the default stats.", "Dein Stats-Embed zeigt die Standard-Stats.", "Tu embed de estadísticas mostrará las estadísticas predeterminadas."]),
+    ("fields.show", ["Your stats embed shows: `{}`", "Dein Stats-Embed zeigt: `{}`", "Tu embed de estadísticas muestra: `{}`"]),
+    ("fields.default", ["You are using the default stats.", "Du verwendest die Standard-Stats.", "Estás usando las estadísticas predeterminadas."]),
+    ("fields.failed", ["Failed to save your stats. Please try again.", "Deine Stats konnten nicht gespeichert werden. Bitte versuche es erneut.", "No se pudieron guardar tus estadísticas. Por favor inténtalo de nuevo."]),
+    //Rivals
+    ("rival.overtake_title", ["Rival Overtake!", "Rivale überholt!", "¡Rival superado!"]),
+    ("rival.overtake_global", ["**{}** just passed their rival **{}** in global rank! (now **#{}**)", "**{}** hat gerade den Rivalen **{}** im globalen Rang überholt! (jetzt **#{}**)", "¡**{}** acaba de superar a su rival **{}** en el rango global! (ahora **#{}**)"]),
+    ("rival.overtake_country", ["**{}** just passed their rival **{}** in country rank! (now **#{}**)", "**{}** hat gerade den Rivalen **{}** im Landesrang überholt! (jetzt **#{}**)", "¡**{}** acaba de superar a su rival **{}** en el rango del país! (ahora **#{}**)"]),
+    ("rival.guild_only", ["Rivals can only be added inside of a server.", "Rivalen können nur auf einem Server hinzugefügt werden.", "Los rivales solo se pueden añadir dentro de un servidor."]),
+    ("rival.yourself", ["You can't be your own rival!", "Du kannst nicht dein eigener Rivale sein!", "¡No puedes ser tu propio rival!"]),
+    ("rival.add_failed", ["Failed to add rival. Please try again.", "Der Rivale konnte nicht hinzugefügt werden. Bitte versuche es erneut.", "No se pudo añadir al rival. Por favor inténtalo de nuevo."]),
+    ("rival.added", ["**{}** (#{}) is now your rival!", "**{}** (#{}) ist jetzt dein Rivale!", "¡**{}** (#{}) ahora es tu rival!"]),
+    ("rival.removed", ["`{}` is no longer your rival.", "`{}` ist nicht mehr dein Rivale.", "`{}` ya no es tu rival."]),
+    ("rival.not_found", ["`{}` is not your rival!", "`{}` ist nicht dein Rivale!", "¡`{}` no es tu rival!"]),
+    ("rival.remove_failed", ["Failed to remove rival. Please try again.", "Der Rivale konnte nicht entfernt werden. Bitte versuche es erneut.", "No se pudo eliminar al rival. Por favor inténtalo de nuevo."]),
+    ("rival.none", ["You have no rivals yet!", "Du hast noch keine Rivalen!", "¡Todavía no tienes rivales!"]),
+    ("rival.list", ["Your rivals:", "Deine Rivalen:", "Tus rivales:"]),
+    //Stats embed
+    ("stats.rank", ["Rank", "Rang", "Rango"]),
+    ("stats.country_rank", ["Country Rank", "Landesrang", "Rango del país"]),
+    ("stats.first_seen", ["First Seen", "Zuerst gesehen", "Visto por primera vez"]),
+    ("stats.account_age", ["{} days old", "{} Tage alt", "{} días"]),
+    ("stats.description", ["Description", "Beschreibung", "Descripción"]),
+    ("stats.stats", ["Stats", "Statistiken", "Estadísticas"]),
+    ("stats.profile_changes", ["Profile Changes", "Profiländerungen", "Cambios de perfil"]),
+    ("event.inactive", ["Became inactive", "Inaktiv geworden", "Se volvió inactivo"]),
+    ("event.active", ["Active again", "Wieder aktiv", "Activo de nuevo"]),
+    ("event.banned", ["Banned", "Gesperrt", "Baneado"]),
+    ("event.unbanned", ["Unbanned", "Entsperrt", "Desbaneado"]),
+    ("event.renamed", ["Renamed: {} → {}", "Umbenannt: {} → {}", "Renombrado: {} → {}"]),
+    ("event.country", ["Country changed: {} → {}", "Land geändert: {} → {}", "País cambiado: {} → {}"]),
+    ("event.avatar", ["Avatar changed", "Avatar geändert", "Avatar cambiado"]),
+    //Metric labels
+    ("metric.pp", ["Performance Points (PP)", "Performance Points (PP)", "Puntos de rendimiento (PP)"]),
+    ("metric.rank", ["Global Rank", "Globaler Rang", "Rango global"]),
+    ("metric.countryRank", ["Country Rank", "Landesrang", "Rango del país"]),
+    ("metric.acc", ["Average Ranked Accuracy", "Durchschnittliche Ranked-Genauigkeit", "Precisión media en ranked"]),
+    ("metric.totalScore", ["Total Score", "Gesamtpunktzahl", "Puntuación total"]),
+    ("metric.rankedScore", ["Total Ranked Score", "Ranked-Gesamtpunktzahl", "Puntuación ranked total"]),
+    ("metric.totalPlays", ["Total Play Count", "Gesamtzahl Spiele", "Partidas totales"]),
+    ("metric.rankedPlays", ["Ranked Play Count", "Ranked-Spiele", "Partidas ranked"]),
+    ("metric.replays", ["Replays Watched", "Angesehene Replays", "Repeticiones vistas"]),
+    ("unit.plays", [" plays", " Spiele", " partidas"]),
+];
+
+impl Locale {
+    //Function to turn a language code into a locale
+    pub fn parse(code: &str) -> Option<Locale> {
+        match code.to_lowercase().as_str() {
+            "en" | "english" => Some(Locale::En),
+            "de" | "deutsch" | "german" => Some(Locale::De),
+            "es" | "español" | "espanol" | "spanish" => Some(Locale::Es),
+            _ => None,
+        }
+    }
+
+    //Function to get the language code of a locale (stored in the database)
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+            Locale::Es => "es",
+        }
+    }
+
+    //Function to get the name of a locale in its own language
+    pub fn name(self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::De => "Deutsch",
+            Locale::Es => "Español",
+        }
+    }
+
+    //Function to look up a message in the catalog (unknown keys are returned as is)
+    pub fn text(self, key: &str) -> &str {
+        match CATALOG.iter().find(|(name, _)| *name == key) {
+            Some((_, texts)) => texts[self as usize],
+            None => key,
+        }
+    }
+
+    //Function to look up a message and fill in its arguments
+    pub fn fill(self, key: &str, args: &[&str]) -> String {
+        let mut parts = self.text(key).split("{}");
+        let mut filled = parts.next().unwrap_or("").to_string();
+        for (index, part) in parts.enumerate() { //Put an argument before every following part
+            filled += args.get(index).copied().unwrap_or("");
+            filled += part;
+        }
+        filled
+    }
+
+    //Function to find the label of a metric (falls back to the English label)
+    pub fn label(self, metric: &crate::metrics::Metric) -> &'static str {
+        let key = format!("metric.{}", metric.name);
+        match CATALOG.iter().find(|(name, _)| *name == key) {
+            Some((_, texts)) => texts[self as usize],
+            None => metric.label,
+        }
+    }
+
+    //Function to translate the unit of a metric
+    pub fn unit(self, unit: &str) -> String {
+        match (unit, self) {
+            ("%", Locale::En) => String::from("%"),
+            ("%", _) => String::from(" %"), //German and Spanish put a space before the percent sign
+            (" plays", _) => self.text("unit.plays").to_string(),
+            _ => unit.to_string(),
+        }
+    }
+
+    //Function to get the separator between groups of three digits
+    fn group_separator(self) -> char {
+        match self {
+            Locale::En => ',',
+            Locale::De | Locale::Es => '.',
+        }
+    }
+
+    //Function to get the separator before decimals
+    fn decimal_separator(self) -> char {
+        match self {
+            Locale::En => '.',
+            Locale::De | Locale::Es => ',',
+        }
+    }
+
+    //Function to format an integer (1,234,567 or 1.234.567)
+    pub fn integer(self, num: i64, include_pos: bool) -> String {
+        group_digits(num, include_pos, self.group_separator())
+    }
+
+    //Function to format a decimal with a fixed amount of places (1,234.56 or 1.234,56)
+    pub fn decimal(self, value: f64, places: usize) -> String {
+        let formatted = format!("{:.*}", places, value.abs()); //Round first so the integer part carries over
+        let (whole, fraction) = formatted.split_once('.').unwrap_or((formatted.as_str(), ""));
+        let mut result = group_digits(whole.parse::<i64>().unwrap_or(0), false, self.group_separator());
+        if value < 0.0 && formatted.chars().any(|c| c != '0' && c != '.') { //Keep the sign unless it rounded to 0
+            result.insert(0, '-');
+        }
+        if !fraction.is_empty() {
+            result.push(self.decimal_separator());
+            result += fraction;
+        }
+        result
+    }
+
+    //Function to format a time (seconds since unix epoch) as a date
+    pub fn date(self, time: i64) -> String {
+        let pattern = match self {
+            Locale::En => "%Y-%m-%d",
+            Locale::De => "%d.%m.%Y",
+            Locale::Es => "%d/%m/%Y",
+        };
+        match DateTime::<Utc>::from_timestamp(time, 0) { //Convert time to a date
+            Some(date) => date.format(pattern).to_string(),
+            None => String::from("?"), //If time is out of range
+        }
+    }
+}
+
+//Function to find the locale of a user in a guild (user setting, then guild setting, then the default)
+pub fn resolve(discord_id: Option<&str>, guild_id: Option<&str>) -> Locale {
+    let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database (tables are created at startup)
+    let user = discord_id.and_then(|discord_id| fetch_locale(&conn, "user", discord_id).ok().flatten());
+    let guild = guild_id.and_then(|guild_id| fetch_locale(&conn, "guild", guild_id).ok().flatten());
+    user.or(guild).and_then(|code| Locale::parse(&code)).unwrap_or(DEFAULT_LOCALE)
+}
+
+//Function to find the locale for replying to a message
+pub fn for_message(msg: &Message) -> Locale {
+    resolve(Some(&msg.author.name), msg.guild_id.map(|guild_id| guild_id.to_string()).as_deref())
+}
+
+//Function to react to the language command (set, reset, show) for the user or the server
+pub async fn language_command(ctx: &Context, msg: &Message, parts: &[&str]) {
+    //Settings apply to the user unless the first word is "server"
+    let (scope, owner, parts) = match parts.first() {
+        Some(&"server") => match msg.guild_id {
+            Some(guild_id) => ("guild", guild_id.to_string(), &parts[1..]),
+            None => {
+                send_simple_format(ctx, msg, for_message(msg).text("language.server_only")).await; //Send error
+                return;
+            }
+        },
+        _ => ("user", msg.author.name.clone(), parts),
+    };
+    let action = parts.first().copied().unwrap_or("show");
+    if scope == "guild" && action != "show" && !can_manage_guild(ctx, msg).await { //Only managers can change the settings of the server
+        send_simple_format(ctx, msg, for_message(msg).text("error.manage_guild")).await; //Send error
+        return;
+    }
+
+    let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+    let result = create_db(&conn).and_then(|_| match action {
+        "show" => Ok(None),
+        "reset" => delete_locale(&conn, scope, &owner).map(|_| None),
+        _ => match Locale::parse(action) {
+            Some(locale) => save_locale(&conn, scope, &owner, locale.code()).map(|_| Some(locale)),
+            None => Ok(None),
+        },
+    });
+    drop(conn); //Close database before sending
+    let locale = for_message(msg); //Reply in the locale that applies now
+    let reply = match (result, action) {
+        (Ok(Some(picked)), _) => {
+            println!("Language `{}` for {} `{}` (!language)", picked.code(), scope, owner); //Print success
+            locale.fill("language.set", &[picked.name()])
+        }
+        (Ok(None), "reset") => locale.text("language.reset").to_string(),
+        (Ok(None), "show") => locale.fill("language.show", &[locale.name()]),
+        (Ok(None), _) => locale.text("language.invalid").to_string(),
+        (Err(e), _) => {
+            println!("Error saving language: {}", e); //Print error
+            locale.text("language.failed").to_string()
+        }
+    };
+    send_simple_format(ctx, msg, &reply).await; //Send reply
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_groups_digits_per_locale() {
+        assert_eq!(Locale::En.integer(1_234_567, false), "1,234,567");
+        assert_eq!(Locale::De.integer(1_234_567, false), "1.234.567");
+        assert_eq!(Locale::Es.integer(-1_234, false), "-1.234");
+        assert_eq!(Locale::En.integer(999, true), "+999");
+        assert_eq!(Locale::En.integer(0, false), "0");
+    }
+
+    #[test]
+    fn decimal_rounds_and_uses_the_separators_of_the_locale() {
+        assert_eq!(Locale::En.decimal(1_234.5, 2), "1,234.50");
+        assert_eq!(Locale::De.decimal(1_234.5, 2), "1.234,50");
+        assert_eq!(Locale::En.decimal(999.999, 2), "1,000.00"); //Rounding carries into the integer part
+        assert_eq!(Locale::En.decimal(-12.345, 1), "-12.3");
+        assert_eq!(Locale::De.decimal(-0.001, 2), "0,00"); //No sign when it rounds to 0
+        assert_eq!(Locale::En.decimal(12.7, 0), "13");
+    }
+
+    #[test]
+    fn fill_puts_the_arguments_in_order() {
+        assert_eq!(Locale::En.fill("goal.set", &["1,000pp", "900pp"]), "Goal set: **1,000pp** (now 900pp)");
+        assert_eq!(Locale::De.text("unknown.key"), "unknown.key");
+    }
+}
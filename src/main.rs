@@ -7,9 +7,31 @@ mod datatweaks; //Fetching and inserting data
 mod compare; //Comparing the new data and the old data (from the database)
 mod format; //Formatting data for the discord bot
 mod message; //Take in message and respond to it
+mod leaderboard; //Ranking linked players of a guild
+mod schedule; //Background jobs (weekly posts)
+mod rivals; //Rivals and overtake callouts
+mod milestones; //Milestone announcements
+mod goals; //Goals and progress tracking
+mod metrics; //Registry of every stat that can be compared
+mod theme; //Embed colors and layouts picked by users and guilds
+mod locale; //Languages and number formats picked by users and guilds
+mod fields; //Stats picked by users for the stats embed
+mod card; //Profile card images
+mod country; //Country leaderboards and statistics
+mod neighbours; //Players ranked around a player
+mod pp; //PP weighting math
+mod reach; //PP needed to reach a rank
+mod maps; //Ranked map catalogue
+mod recommend; //Map recommendations for pp farming
+mod snipe; //Maps to snipe from another player
+mod playlist; //Beat Saber playlist export
+mod accuracy; //Accuracy by star rating
+mod mapboard; //Guild leaderboards of single maps
+mod session; //Play session recaps while tracking
+mod activity; //Play streaks, calendars and inactivity reminders
 
 //Storing the data from ScoreSaber API
-#[derive(Debug, Serialize, Deserialize, Default)] //This stores data under ScoreStats
+#[derive(Debug, Clone, Serialize, Deserialize, Default)] //This stores data under ScoreStats
 pub struct ScoreStats {
     totalScore: i64,
     totalRankedScore: i64,
@@ -19,7 +41,7 @@ pub struct ScoreStats {
     replaysWatched: i64,
 }
 //
-#[derive(Debug, Serialize, Deserialize, Default)] //This is where all the data is stored (PlayerData)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)] //This is where all the data is stored (PlayerData)
 pub struct PlayerData {
     id: String,
     name: String,
@@ -38,37 +60,232 @@ pub struct PlayerData {
 #[derive(Debug, Serialize, Deserialize, Default)] //This is used for storing multiple players (for search function)
 pub struct PlayersData {
     players: Vec<PlayerData>,
+    #[serde(default)] //Search is fetched without metadata
+    metadata: Metadata,
+}
+
+//Storing the scores from ScoreSaber API
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(default)] //Missing fields use their default
+pub struct Score {
+    id: i64,
+    rank: i64,
+    baseScore: i64,
+    modifiedScore: i64,
+    pp: f64,
+    weight: f64,
+    modifiers: String,
+    multiplier: f64,
+    badCuts: i64,
+    missedNotes: i64,
+    maxCombo: i64,
+    fullCombo: bool,
+    timeSet: String,
+}
+//
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(default)] //Missing fields use their default
+pub struct Difficulty {
+    leaderboardId: i64,
+    difficulty: i64, //1 Easy, 3 Normal, 5 Hard, 7 Expert, 9 Expert+
+    gameMode: String,
+    difficultyRaw: String,
+}
+//
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(default)] //Missing fields use their default
+pub struct LeaderboardInfo { //This is a map (song and difficulty)
+    id: i64,
+    songHash: String,
+    songName: String,
+    songSubName: String,
+    songAuthorName: String,
+    levelAuthorName: String,
+    difficulty: Difficulty,
+    maxScore: i64,
+    rankedDate: Option<String>,
+    ranked: bool,
+    stars: f64,
+    coverImage: String,
+}
+//
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(default)] //Missing fields use their default
+pub struct LeaderboardInfoCollection { //This is used for storing a page of maps
+    leaderboards: Vec<LeaderboardInfo>,
+    metadata: Metadata,
+}
+//
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(default)] //Missing fields use their default
+pub struct PlayerScore { //One score of a player and the map it was set on
+    score: Score,
+    leaderboard: LeaderboardInfo,
+}
+//
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(default)] //Missing fields use their default
+pub struct Metadata { //Used for paging
+    total: i64,
+    page: i64,
+    itemsPerPage: i64,
+}
+//
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(default)] //Missing fields use their default
+pub struct PlayerScoreCollection { //This is used for storing a page of scores
+    playerScores: Vec<PlayerScore>,
+    metadata: Metadata,
+}
+
+//Struct for a ranked map stored in the map catalogue (one row per difficulty)
+#[derive(Debug, Clone, Default)]
+pub struct RankedMap {
+    leaderboardId: i64,
+    songHash: String,
+    songName: String,
+    songSubName: String,
+    songAuthorName: String,
+    levelAuthorName: String,
+    difficulty: i64,
+    gameMode: String,
+    stars: f64,
+    maxScore: i64,
+    rankedDate: i64, //Seconds since unix epoch
+    coverImage: String,
+    ranked: bool, //False once the map is unranked
+    lastSeen: i64, //Last time the map was seen in the ranked list
+}
+
+//Struct for a score stored in the score cache (one row per player and map)
+#[derive(Debug, Clone, Default)]
+pub struct CachedScore {
+    leaderboardId: i64,
+    baseScore: i64,
+    modifiedScore: i64, //Score with modifiers (what the leaderboards rank by)
+    modifiers: String,
+    pp: f64,
+    maxScore: i64,
+    stars: f64,
+    songName: String,
+    levelAuthorName: String,
+    difficulty: i64,
+}
+
+//Struct for a stored snapshot of a player's stats (history)
+#[derive(Debug, Serialize, Deserialize, Default, Clone)] //This stores one row of the snapshots table
+pub struct Snapshot {
+    timestamp: i64,
+    pp: f64,
+    rank: i64,
+    countryRank: i64,
+    averageRankedAccuracy: f64,
+    totalScore: i64,
+    totalRankedScore: i64,
+    totalPlayCount: i64,
+    rankedPlayCount: i64,
+    replaysWatched: i64,
+}
+
+//Struct for a goal set by a discord
+#[derive(Debug, Serialize, Deserialize, Default)] //This stores one row of the goals table
+pub struct Goal {
+    metric: String,
+    target: f64,
+    start: f64, //Value when the goal was set (used for the progress bar)
+    created: i64,
+    deadline: Option<i64>,
+    reached: Option<i64>,
+}
+
+//Struct for an inactivity reminder a discord opted into
+#[derive(Debug, Default)] //This stores one row of the reminders table (with the linked player)
+pub struct Reminder {
+    discord: String,
+    user: String, //Discord user id (used to mention)
+    channel: String, //Channel the reminder is posted in
+    days: i64, //Days without a score before reminding
+    reminded: i64, //Last time a reminder was sent
+    player: String, //Linked ScoreSaber account
+}
+
+//Enum for changes to the profile that are not numbers
+#[derive(Debug, Serialize, Deserialize)]
+enum ProfileEvent {
+    Inactive(bool), //True if became inactive, false if active again
+    Banned(bool), //True if banned, false if unbanned
+    Renamed { old: String, new: String },
+    CountryChanged { old: String, new: String },
+    AvatarChanged,
+}
+
+//Struct for one metric that changed in new-old
+#[derive(Debug)]
+struct Change {
+    metric: &'static metrics::Metric,
+    old: f64,
+    new: f64,
 }
 
 //Struct for changes in new-old
-#[derive(Debug, Serialize, Deserialize, Default)] //This struct has default in case of new user
-struct Changes{
-    pp: bool,
-    pp_change: f64,
-    rAccuracy: bool,
-    rAccuracy_change: f64,
-    rank: bool,
-    rank_change: i64,
-    cRank: bool,
-    cRank_change: i64,
-    tScore: bool,
-    tScore_change: i64,
-    rScore: bool,
-    rScore_change: i64,
-    tCount: bool,
-    tCount_change: i64,
-    rCount: bool,
-    rCount_change: i64,
-    replays: bool,
-    replays_change: i64,
+#[derive(Debug, Default)] //This struct has default in case of new user
+struct Changes {
+    metrics: Vec<Change>, //Every metric that changed (in the order of METRICS)
+    events: Vec<ProfileEvent>,
+}
+//
+impl Changes {
+    //Function to find the change of a metric by name (None if it did not change)
+    fn get(&self, name: &str) -> Option<&Change> {
+        self.metrics.iter().find(|change| change.metric.name == name)
+    }
+}
+
+//
+impl PlayerData {
+    //Function to take the numeric stats out of the player data
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            timestamp: datatweaks::current_time(),
+            pp: self.pp,
+            rank: self.rank,
+            countryRank: self.countryRank,
+            averageRankedAccuracy: self.scoreStats.averageRankedAccuracy,
+            totalScore: self.scoreStats.totalScore,
+            totalRankedScore: self.scoreStats.totalRankedScore,
+            totalPlayCount: self.scoreStats.totalPlayCount,
+            rankedPlayCount: self.scoreStats.rankedPlayCount,
+            replaysWatched: self.scoreStats.replaysWatched,
+        }
+    }
+}
+
+//Function to find the accuracy of a score from its score without modifiers (0 to 1, None if the map has no max score)
+fn score_accuracy(base_score: i64, max_score: i64) -> Option<f64> {
+    if max_score > 0 { Some(base_score as f64 / max_score as f64) } else { None }
+}
+//
+impl PlayerScore {
+    //Function to find the accuracy of the score (0 to 1, None if the map has no max score)
+    fn accuracy(&self) -> Option<f64> {
+        score_accuracy(self.score.baseScore, self.leaderboard.maxScore)
+    }
+}
+//
+impl CachedScore {
+    //Function to find the accuracy of the score (0 to 1, None if the map has no max score)
+    fn accuracy(&self) -> Option<f64> {
+        score_accuracy(self.baseScore, self.maxScore)
+    }
 }
 
 pub struct Handler;// This struct is used for discord bot events
 //
 #[async_trait]
 impl EventHandler for Handler{ //Implement EventHandler to handle discord events
-    async fn ready(&self, _ctx: Context, ready: Ready) { //When discord triggers ready event
+    async fn ready(&self, ctx: Context, ready: Ready) { //When discord triggers ready event
         println!("\n{} is connected!\n", ready.user.name); //Prints ready and name of bot
+        schedule::start(ctx); //Start the background jobs
     }
     async fn message(&self, ctx: Context, msg: Message) { //Handle incoming messages
         let message = msg.content.as_str().split(' ').next().unwrap(); // First word of message taken in
@@ -78,8 +295,8 @@ impl EventHandler for Handler{ //Implement EventHandler to handle discord events
     }
 }
 
-//Function for sending or tracking stats
-pub async fn send_stats(player_id: &str, ctx: Context, msg: Message) -> bool{
+//Function for sending or tracking stats (only_on_change skips the message if none of the stats picked with !fields changed)
+pub async fn send_stats(player_id: &str, ctx: Context, msg: Message, only_on_change: bool) -> bool{
     let mut payload_new_user = true; //If new user
     let mut payload_data = PlayerData::default(); //Default data
     let mut payload_changes = Changes::default(); //Default changes
@@ -95,11 +312,16 @@ pub async fn send_stats(player_id: &str, ctx: Context, msg: Message) -> bool{
         Ok(data_from_db) => { //If functioned correctly
             match datatweaks::fetch_player_data(player_id).await { //Checks if fetching data from ScoreSabere API goes successfully
                 Ok(data) => { //If functioned correctly
-                    if let Err(e) = datatweaks::insert_player_data(&conn, &data, false) { //Call function to insert data into databas
-                        println!("Error inserting data into database: {}", e); //Prints if inputing data into database results in failure
-                    }
+                    let events = match datatweaks::insert_player_data(&conn, &data, false) { //Call function to insert data into databas
+                        Ok(events) => events, //Profile events (already in the profile history)
+                        Err(e) => {
+                            println!("Error inserting data into database: {}", e); //Prints if inputing data into database results in failure
+                            compare::compare_profile(&data, &data_from_db) //Still show the events
+                        }
+                    };
                     match compare::compare_data(&data, &data_from_db) { //Call function to compare data
-                        Ok(changes) => { //If functioned correctly
+                        Ok(mut changes) => { //If functioned correctly
+                            changes.events = events; //Changes to the profile that are not numbers
                             payload_new_user = false; //Not new user
                             payload_data = data; //Set data
                             payload_changes = changes; //Set changes
@@ -132,15 +354,38 @@ pub async fn send_stats(player_id: &str, ctx: Context, msg: Message) -> bool{
         }
         Err(e) => println!("Error fetching player data from database: {}", e), //Prints if fetching data from database results in failure
     }
-    let payload = format::formatdata(&payload_data, &payload_changes, payload_new_user); //Make payload using function to format data
-    if let Err(why) = msg.channel_id.send_message(&ctx.http, payload).await { //If sending message has error
-        println!("Error sending message: {:?}", why); //Print error
+    let theme = theme::for_message(&msg); //Colors and layout of the embeds
+    let locale = locale::for_message(&msg); //Language and number format
+    let fields = fields::for_message(&msg); //Stats picked by the user
+    let changed = fields.is_none() || payload_new_user || !payload_changes.events.is_empty() || fields::selected_changed(&payload_changes, fields.as_deref()); //Without picked stats every message is sent
+    if changed || !only_on_change { //If there is something worth sending
+        let mut payload = format::formatdata(&payload_data, &payload_changes, payload_new_user, &theme, locale, fields.as_deref()); //Make payload using function to format data
+        if let Some(goals) = goals::goals_embed(&payload_data, &theme, locale) { //If the player has goals then show their progress
+            payload = payload.add_embed(goals);
+        }
+        if let Err(why) = msg.channel_id.send_message(&ctx.http, payload).await { //If sending message has error
+            println!("Error sending message: {:?}", why); //Print error
+        }
+    }
+    if !payload_new_user { //If there were changes then check for rivals passed and milestones reached
+        rivals::announce_overtakes(&ctx, &msg, &payload_data, &payload_changes).await;
+        milestones::announce_milestones(&ctx, &msg, &payload_data, &payload_changes).await;
     }
+    goals::announce_goals(&ctx, &msg, &payload_data).await; //Congratulate on any goals reached
     true
 }
 
 //Function to start the client
+#[allow(clippy::needless_return)] //Original early returns
 async fn start_client() {
+    //Create the tables once so settings can be looked up for every message without it
+    let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+    if let Err(e) = datatweaks::create_db(&conn) { //Function to create database if not exists
+        println!("Failed to initialize database: {}", e); //Failed to create database
+        return;
+    }
+    drop(conn); //Close database before starting
+
     // Check if the token is properly retrieved
     let token = match env::var("DISCORD_TOKEN") { //Retrieve token from environment
         Ok(token) => token, // If works then set token
@@ -151,7 +396,7 @@ async fn start_client() {
     };
 
     // Enable necessary intents
-    let intents = GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT; // Specifies events the bot will respond to
+    let intents = GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT | GatewayIntents::GUILD_MEMBERS; // Specifies events the bot will respond to (members are needed for the member lists of leaderboards)
 
     let mut client = match Client::builder(&token, intents) //Set client and specify token/intents
         .event_handler(Handler) //Impl EventHandler
@@ -0,0 +1,191 @@
+use crate::CachedScore; //CachedScore struct
+use crate::RankedMap; //RankedMap struct
+use crate::imports::*; //Imports
+use crate::datatweaks::{create_db, current_time, fetch_guild_discords, fetch_guild_player_ids, fetch_score_sync, fetch_map, fetch_map_scores, fetch_player_data_from_db, search_maps, sync_score_cache};
+use crate::format::difficulty_name; //Function to name a difficulty
+use crate::message::send_simple_format; //Function to send a simple embedded message
+use crate::locale::{self, Locale};
+use crate::maps::leaderboard_url; //Function to link the leaderboard of a map
+use crate::theme; //Embed themes
+
+const MAX_ROWS: usize = 20; //Players shown on the mapboard
+const STALE_AFTER: i64 = 900; //Seconds before a score cache is refreshed again
+
+//What the mapboard is ranked by
+#[derive(Clone, Copy, PartialEq)]
+enum Sort {
+    Score, //Score with modifiers (like ScoreSaber)
+    Accuracy, //Score without modifiers over the max score
+}
+
+//One linked player's score on the map
+struct Entry {
+    name: String,
+    discord: String,
+    score: CachedScore,
+}
+
+//Function to find the map of a leaderboard id or a song search (the map and the other difficulties of the song)
+fn find_map(conn: &Connection, query: &str) -> Result<Option<(RankedMap, Vec<RankedMap>)>> {
+    if let Ok(leaderboard_id) = query.parse::<i64>() { //Leaderboard id
+        if let Some(map) = fetch_map(conn, leaderboard_id)? {
+            return Ok(Some((map, Vec::new())));
+        }
+        //Not in the ranked catalogue, use a cached score for the song info
+        return Ok(fetch_map_scores(conn, leaderboard_id)?.into_iter().next().map(|(_, score)| (RankedMap {
+            leaderboardId: score.leaderboardId,
+            songName: score.songName,
+            levelAuthorName: score.levelAuthorName,
+            difficulty: score.difficulty,
+            stars: score.stars,
+            maxScore: score.maxScore,
+            ..Default::default()
+        }, Vec::new())));
+    }
+    let mut maps = search_maps(conn, query)?.into_iter(); //Song search
+    Ok(maps.next().map(|map| {
+        let others = maps.filter(|other| other.songHash == map.songHash).collect();
+        (map, others)
+    }))
+}
+
+//Function to collect the cached scores of the linked players of a guild on a map
+fn collect_entries(conn: &Connection, discords: &HashSet<String>, leaderboard_id: i64) -> Result<Vec<Entry>> {
+    let members: HashMap<String, String> = fetch_guild_player_ids(conn, discords)?.into_iter().collect(); //Player and discord
+    let mut entries = Vec::new();
+    for (player_id, score) in fetch_map_scores(conn, leaderboard_id)? {
+        if let Some(discord) = members.get(&player_id) { //Only linked players of the guild
+            let name = fetch_player_data_from_db(conn, &player_id).map(|data| data.name).unwrap_or(player_id);
+            entries.push(Entry { name, discord: discord.clone(), score });
+        }
+    }
+    Ok(entries)
+}
+
+//Function to find the linked players whose score cache is missing or older than STALE_AFTER
+fn stale_players(conn: &Connection, members: &[(String, String)]) -> Result<Vec<String>> {
+    let mut stale = Vec::new();
+    for (player_id, _) in members {
+        if fetch_score_sync(conn, player_id)?.is_none_or(|time| current_time() - time > STALE_AFTER) {
+            stale.push(player_id.clone());
+        }
+    }
+    Ok(stale)
+}
+
+//Function to refresh score caches side by side (returns how many failed)
+async fn refresh_caches(players: Vec<String>) -> usize {
+    let mut syncs = tokio::task::JoinSet::new();
+    for player_id in players {
+        syncs.spawn(async move { sync_score_cache(&player_id).await });
+    }
+    let mut failed = 0;
+    while let Some(result) = syncs.join_next().await {
+        if !matches!(result, Ok(true)) { //Sync failed or panicked
+            failed += 1;
+        }
+    }
+    failed
+}
+
+//Function to format one row of the mapboard
+fn format_entry(position: usize, entry: &Entry, sort: Sort, locale: Locale) -> String {
+    let acc = entry.score.accuracy().map(|acc| format!("{}{}", locale.decimal(acc * 100.0, 2), locale.unit("%"))).unwrap_or(String::from("?"));
+    let score = locale.integer(entry.score.modifiedScore, false);
+    let (main, other) = if sort == Sort::Score { (score, acc) } else { (acc, score) };
+    let mut line = format!("**{}.** {} (`{}`) - **{}** ({})", position + 1, entry.name, entry.discord, main, other);
+    if !entry.score.modifiers.is_empty() {
+        line += &format!(" `{}`", entry.score.modifiers);
+    }
+    if entry.score.pp > 0.0 {
+        line += &format!(" {}pp", locale.decimal(entry.score.pp, 2));
+    }
+    line
+}
+
+//Function to send how the linked players of the guild scored on a map (params are the id or song and the sort)
+pub async fn send_mapboard(ctx: &Context, msg: &Message, params: &[&str]) {
+    let locale = locale::for_message(msg); //Language and number format
+    let guild_id = match msg.guild_id { //Mapboards only exist inside of a server
+        Some(guild_id) => guild_id,
+        None => {
+            send_simple_format(ctx, msg, locale.text("mapboard.server_only")).await; //Send error
+            return;
+        }
+    };
+    let (sort, query) = match params.split_last() { //The sort is optional and comes last
+        Some((last, rest)) if !rest.is_empty() && last.eq_ignore_ascii_case("acc") => (Sort::Accuracy, rest.join(" ")),
+        Some((last, rest)) if !rest.is_empty() && last.eq_ignore_ascii_case("score") => (Sort::Score, rest.join(" ")),
+        _ => (Sort::Score, params.join(" ")),
+    };
+    let discords = match fetch_guild_discords(ctx, guild_id).await { //Members of the guild
+        Ok(discords) => discords,
+        Err(e) => {
+            println!("Error fetching guild members: {}", e); //Print error
+            send_simple_format(ctx, msg, locale.text("mapboard.members_failed")).await; //Send error
+            return;
+        }
+    };
+    let found = {
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        create_db(&conn).and_then(|_| {
+            let members = fetch_guild_player_ids(&conn, &discords)?;
+            let stale = stale_players(&conn, &members)?;
+            Ok((find_map(&conn, &query)?, members, stale))
+        })
+    };
+    let (map, others, members, stale) = match found {
+        Ok((Some((map, others)), members, stale)) => (map, others, members, stale),
+        Ok((None, _, _)) => {
+            send_simple_format(ctx, msg, format!("{} (`!mapboard` `leaderboard_id|song` `score|acc [OPTIONAL]`)", locale.fill("mapboard.not_found", &[&query])).as_str()).await; //Send error
+            return;
+        }
+        Err(e) => {
+            println!("Error fetching map from database: {}", e); //Print error
+            send_simple_format(ctx, msg, locale.text("mapboard.map_failed")).await; //Send error
+            return;
+        }
+    };
+    if members.is_empty() { //If nobody in the guild is linked
+        send_simple_format(ctx, msg, format!("{} (`!link` `player_id`)", locale.text("mapboard.no_linked")).as_str()).await; //Send error
+        return;
+    }
+    let failed = refresh_caches(stale).await; //Refresh the outdated score caches of the linked players
+    let entries = {
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        collect_entries(&conn, &discords, map.leaderboardId)
+    };
+    let mut entries = match entries {
+        Ok(entries) => entries,
+        Err(e) => {
+            println!("Error fetching mapboard from database: {}", e); //Print error
+            send_simple_format(ctx, msg, locale.text("mapboard.failed")).await; //Send error
+            return;
+        }
+    };
+    match sort {
+        Sort::Score => entries.sort_by_key(|entry| std::cmp::Reverse(entry.score.modifiedScore)),
+        Sort::Accuracy => entries.sort_by(|a, b| b.score.accuracy().unwrap_or(0.0).total_cmp(&a.score.accuracy().unwrap_or(0.0))),
+    }
+
+    let lines: Vec<String> = entries.iter().take(MAX_ROWS).enumerate().map(|(position, entry)| format_entry(position, entry, sort, locale)).collect();
+    let stars = if map.stars > 0.0 { format!(" {}★", locale.decimal(map.stars, 2)) } else { String::new() }; //Unranked maps have no stars
+    let mut embed = CreateEmbed::new() //Make Embed
+        .color(theme::for_message(msg).color) //Set color to the theme
+        .title(locale.fill("mapboard.title", &[&map.songName, difficulty_name(map.difficulty), &stars])) //Set title
+        .url(leaderboard_url(map.leaderboardId)) //Link to the ScoreSaber leaderboard
+        .description(if lines.is_empty() { locale.text("mapboard.nobody").to_string() } else { lines.join("\n") });
+    let mut footer = locale.fill("mapboard.footer", &[locale.text(if sort == Sort::Score { "mapboard.by_score" } else { "mapboard.by_acc" }), &entries.len().to_string(), &members.len().to_string()]);
+    if !others.is_empty() { //Other difficulties of the song can be picked by id
+        let others: Vec<String> = others.iter().map(|other| format!("{} ({})", difficulty_name(other.difficulty), other.leaderboardId)).collect();
+        footer += &locale.fill("mapboard.others", &[&others.join(", ")]);
+    }
+    if failed > 0 { //Scores of these players may be missing or outdated
+        footer += &locale.fill("mapboard.sync_failed", &[&failed.to_string()]);
+    }
+    embed = embed.footer(CreateEmbedFooter::new(footer));
+    if let Err(why) = msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await { // If sending message has error
+        println!("Error sending message: {:?}", why); // Print error
+    }
+    println!("Mapboard of `{}` sent to Discord (!mapboard)", map.songName); //Print success
+}
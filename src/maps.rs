@@ -0,0 +1,186 @@
+use crate::LeaderboardInfo; //LeaderboardInfo struct
+use crate::RankedMap; //RankedMap struct
+use crate::imports::*; //Imports
+use crate::datatweaks::{create_db, current_time, fetch_announce_channels, fetch_map, fetch_map_sync, fetch_ranked_maps_page, save_map, set_map_sync, unrank_maps_not_seen_since};
+use crate::format::difficulty_name; //Function to name a difficulty
+use crate::locale::{self, Locale};
+use crate::playlist::playlist_attachment; //Function to export maps as a playlist
+use crate::theme; //Embed themes
+
+const FULL_SYNC_EVERY: i64 = 86_400; //Every ranked map is checked once a day (to find re-rates and unranks)
+const PAGE_DELAY: u64 = 250; //Milliseconds between pages so the ScoreSaber rate limit is not hit
+const MAPS_PER_EMBED: usize = 15; //Maps listed in each announcement embed
+
+//Maps that changed during a sync
+#[derive(Debug, Default)]
+pub struct MapChanges {
+    pub ranked: Vec<RankedMap>, //Newly ranked
+    pub rerated: Vec<(RankedMap, f64)>, //Stars changed (map and old stars)
+    pub unranked: Vec<RankedMap>, //No longer ranked (only found by full syncs)
+    pub initial: bool, //No full sync has finished yet, every map is new
+}
+
+//Function to turn a map from the ScoreSaber API into a row of the catalogue
+fn to_ranked_map(info: &LeaderboardInfo, now: i64) -> RankedMap {
+    RankedMap {
+        leaderboardId: info.id,
+        songHash: info.songHash.clone(),
+        songName: info.songName.clone(),
+        songSubName: info.songSubName.clone(),
+        songAuthorName: info.songAuthorName.clone(),
+        levelAuthorName: info.levelAuthorName.clone(),
+        difficulty: info.difficulty.difficulty,
+        gameMode: info.difficulty.gameMode.clone(),
+        stars: info.stars,
+        maxScore: info.maxScore,
+        rankedDate: info.rankedDate.as_deref().and_then(|date| DateTime::parse_from_rfc3339(date).ok()).map(|date| date.timestamp()).unwrap_or(0),
+        coverImage: info.coverImage.clone(),
+        ranked: true,
+        lastSeen: now,
+    }
+}
+
+//Function to store a page of ranked maps (returns false if nothing on the page was new or changed)
+fn store_page(conn: &Connection, page: &[LeaderboardInfo], now: i64, changes: &mut MapChanges) -> Result<bool> {
+    let mut changed = false;
+    for info in page { //Loop through every map of the page
+        let map = to_ranked_map(info, now);
+        match fetch_map(conn, map.leaderboardId)? {
+            Some(old) if old.ranked && (old.stars - map.stars).abs() < 0.005 => {} //Same as before
+            Some(old) if old.ranked => { //Stars changed
+                changes.rerated.push((map.clone(), old.stars));
+                changed = true;
+            }
+            _ => { //Never seen or ranked again
+                changes.ranked.push(map.clone());
+                changed = true;
+            }
+        }
+        save_map(conn, &map)?; //Store the map (and when it was last seen)
+    }
+    Ok(changed)
+}
+
+//Function to sync the ranked map catalogue (full syncs go through every page, otherwise it stops at the first page without changes)
+pub async fn sync_maps() -> Option<MapChanges> {
+    let now = current_time();
+    let (initial, full) = {
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        match create_db(&conn).and_then(|_| fetch_map_sync(&conn, "full")) { //Initial until a full sync finished (a failed one leaves maps behind)
+            Ok(last_full) => (last_full.is_none(), last_full.is_none_or(|time| now - time >= FULL_SYNC_EVERY)),
+            Err(e) => {
+                println!("Error fetching map sync state from database: {}", e); //Print error
+                return None;
+            }
+        }
+    };
+
+    let mut changes = MapChanges { initial, ..Default::default() };
+    let mut complete = true; //False if a page failed (stored pages are still announced)
+    for page in 1.. { //Loop through the pages, newest ranked first
+        let data = match fetch_ranked_maps_page(page).await { //Fetch the page from ScoreSaber API
+            Ok(data) => data,
+            Err(e) => {
+                println!("Error fetching ranked maps from API (page {}): {}", page, e); //Print error
+                complete = false;
+                break;
+            }
+        };
+        if data.leaderboards.is_empty() { //Past the last page
+            break;
+        }
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        let changed = match store_page(&conn, &data.leaderboards, now, &mut changes) {
+            Ok(changed) => changed,
+            Err(e) => {
+                println!("Error storing ranked maps: {}", e); //Print error
+                complete = false;
+                break;
+            }
+        };
+        drop(conn); //Close database before waiting
+        if !full && !changed { //Everything older is already stored
+            break;
+        }
+        if data.metadata.itemsPerPage > 0 && page * data.metadata.itemsPerPage >= data.metadata.total { //Last page
+            break;
+        }
+        sleep(Duration::from_millis(PAGE_DELAY)).await; //Waits before the next page
+    }
+
+    let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+    let finished = if full && complete { //Maps not seen during a full sync are no longer ranked (a partial one can't tell)
+        unrank_maps_not_seen_since(&conn, now).and_then(|unranked| {
+            changes.unranked = unranked;
+            set_map_sync(&conn, "full", now)
+        })
+    } else {
+        set_map_sync(&conn, "incremental", now)
+    };
+    if let Err(e) = finished {
+        println!("Error finishing map sync: {}", e); //Print error
+    }
+    println!(
+        "Map catalogue synced ({}{}): {} newly ranked, {} re-rated, {} unranked",
+        if changes.initial { "initial" } else if full { "full" } else { "incremental" }, if complete { "" } else { ", partial" },
+        changes.ranked.len(), changes.rerated.len(), changes.unranked.len()
+    ); //Print summary
+    Some(changes)
+}
+
+//Function to make the link to the ScoreSaber leaderboard of a map
+pub fn leaderboard_url(leaderboard_id: i64) -> String {
+    format!("https://scoresaber.com/leaderboard/{}", leaderboard_id)
+}
+
+//Function to format a map as one line (linked song name, mapper, difficulty and stars)
+pub fn format_map(map: &RankedMap, locale: Locale) -> String {
+    let name = if map.songSubName.is_empty() { map.songName.clone() } else { format!("{} {}", map.songName, map.songSubName) };
+    locale.fill("maps.line", &[&name, &leaderboard_url(map.leaderboardId), &map.levelAuthorName, difficulty_name(map.difficulty), &locale.decimal(map.stars, 2)])
+}
+
+//Function to make the announcement embeds of one kind of change (split so no embed gets too long)
+fn change_embeds(title: &str, lines: &[String], color: u32, locale: Locale) -> Vec<CreateEmbed> {
+    lines.chunks(MAPS_PER_EMBED).enumerate().map(|(index, chunk)| {
+        let title = if index == 0 { format!("{} ({})", title, lines.len()) } else { locale.fill("maps.continued", &[title]) };
+        CreateEmbed::new().color(color).title(title).description(chunk.join("\n"))
+    }).collect()
+}
+
+//Function to announce newly ranked, re-rated and unranked maps in every guild with an announcement channel
+pub async fn announce_map_changes(ctx: &Context, changes: &MapChanges) {
+    if changes.initial { //The first sync finds every map, that is not news
+        return;
+    }
+    let channels = {
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        create_db(&conn).and_then(|_| fetch_announce_channels(&conn)).unwrap_or_default()
+    };
+    let playlist = playlist_attachment(&format!("Newly Ranked {}", Utc::now().format("%Y-%m-%d")), "Maps ranked since the last sync", &changes.ranked).await; //Same playlist for every guild
+    for (guild_id, channel_id, _) in channels { //Loop through every guild with an announcement channel
+        let channel = match channel_id.parse::<u64>() { //If the stored channel is valid
+            Ok(channel) => ChannelId::new(channel),
+            Err(_) => continue,
+        };
+        let locale = locale::resolve(None, Some(&guild_id)); //Language and number format of the guild
+        let color = theme::resolve(None, Some(&guild_id)).accent; //Color of the guild's announcements
+        let ranked: Vec<String> = changes.ranked.iter().map(|map| format_map(map, locale)).collect();
+        let rerated: Vec<String> = changes.rerated.iter().map(|(map, old)| locale.fill("maps.was", &[&format_map(map, locale), &locale.decimal(*old, 2)])).collect();
+        let unranked: Vec<String> = changes.unranked.iter().map(|map| format_map(map, locale)).collect();
+        let mut embeds = change_embeds(locale.text("maps.ranked"), &ranked, color, locale);
+        embeds.extend(change_embeds(locale.text("maps.rerated"), &rerated, color, locale));
+        embeds.extend(change_embeds(locale.text("maps.unranked"), &unranked, color, locale));
+        for (index, embed) in embeds.into_iter().enumerate() { //Send every embed
+            let mut message = CreateMessage::new().embed(embed);
+            if let (0, Some(playlist)) = (index, &playlist) { //Newly ranked maps come first so the playlist goes with them
+                message = message.add_file(playlist.clone());
+            }
+            if let Err(why) = channel.send_message(&ctx.http, message).await { // If sending message has error
+                println!("Error sending map announcement: {:?}", why); // Print error
+            }
+        }
+        if !ranked.is_empty() || !rerated.is_empty() || !unranked.is_empty() {
+            println!("Map changes sent to Discord [Guild: {}]", guild_id); // Prints if successful
+        }
+    }
+}
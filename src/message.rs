@@ -4,6 +4,26 @@ use crate::datatweaks::link_discord;
 use crate::datatweaks::delete_discord_if_exists;
 use crate::datatweaks::discord_linked;
 use crate::datatweaks::search_player;
+use crate::datatweaks::{create_db, current_time, fetch_announce_channel, fetch_player_data_from_db, set_announce_channel};
+use crate::rivals::rival_command;
+use crate::milestones::milestone_command;
+use crate::goals::goal_command;
+use crate::metrics; //Registry of every metric
+use crate::schedule::week_start;
+use crate::leaderboard::send_leaderboard;
+use crate::theme::{self, theme_command};
+use crate::fields::fields_command;
+use crate::card::send_card;
+use crate::locale::{self, language_command};
+use crate::country::{send_country_leaderboard, send_country_stats};
+use crate::neighbours::send_neighbours;
+use crate::reach::send_reach;
+use crate::recommend::{self, send_recommendations, send_unplayed};
+use crate::snipe::send_snipes;
+use crate::accuracy::send_acc_profile;
+use crate::mapboard::send_mapboard;
+use crate::session::{send_recap, SessionTracker, MAX_SESSION_GAP, SESSION_GAP};
+use crate::activity::{remind_command, send_calendar, send_streak};
 
 //Static variables
 pub static mut TRACK: bool = true; //Used to stop tracking
@@ -11,10 +31,57 @@ pub static mut COUNT: i64 = 0; //Goes up every time stats sent successfully
 pub static mut TRACKING: bool = false; //Shows if bot is tracking
 pub static mut LINKED: bool = false; //Shows if discord is linked to a ScoreSaber account
 
+//Commands listed by !help (name, usage), descriptions are in the message catalog
+const HELP: [(&str, &str); 28] = [
+    ("HELP", "`!help`"),
+    ("STATS", "`!stats + player_id [NOTLINKED]`"),
+    ("TRACK", "`!track + player_id [NOTLINKED] + time + session gap like 30m [OPTIONAL]`"),
+    ("UNTRACK", "`!untrack`"),
+    ("LINK", "`!link + player_id`"),
+    ("UNLINK", "`!unlink`"),
+    ("ID", "`!id + player_name`"),
+    ("LEADERBOARD", "`!leaderboard + sort [OPTIONAL]`"),
+    ("RIVAL", "`!rival + add/remove + player_id` or `!rival list`"),
+    ("MILESTONE", "`!milestone + add/remove + metric + value` or `!milestone list`"),
+    ("GOAL", "`!goal set + metric + target + date [OPTIONAL]`, `!goal remove + metric` or `!goal list`"),
+    ("THEME", "`!theme + server [OPTIONAL] + color/accent/layout + value`, `!theme reset` or `!theme show`"),
+    ("FIELDS", "`!fields set + stats...`, `!fields reset` or `!fields show`"),
+    ("CARD", "`!card + player_id [NOTLINKED]`"),
+    ("COUNTRY", "`!country + country_code + page [OPTIONAL]`"),
+    ("COUNTRY-STATS", "`!country-stats + country_code`"),
+    ("NEIGHBOURS", "`!neighbours + player_id [NOTLINKED] + global/country [OPTIONAL]`"),
+    ("REACH", "`!reach + player_id [NOTLINKED] + rank + raw_pp [OPTIONAL] + global|country [OPTIONAL]`"),
+    ("RECOMMEND", "`!recommend + player_id [NOTLINKED] + count [OPTIONAL] + min-max stars [OPTIONAL]`"),
+    ("SNIPE", "`!snipe + player_id [NOTLINKED] + target_id`"),
+    ("UNPLAYED", "`!unplayed + player_id [NOTLINKED] + min-max stars`"),
+    ("ACC-PROFILE", "`!acc-profile + player_id [NOTLINKED]`"),
+    ("MAPBOARD", "`!mapboard + leaderboard_id/song + score/acc [OPTIONAL]`"),
+    ("STREAK", "`!streak + player_id [NOTLINKED]`"),
+    ("CALENDAR", "`!calendar + player_id [NOTLINKED]`"),
+    ("REMIND", "`!remind on + days [OPTIONAL]` or `!remind off`"),
+    ("LANGUAGE", "`!language + server [OPTIONAL] + en/de/es`, `!language reset` or `!language show`"),
+    ("SETCHANNEL", "`!setchannel`"),
+];
+
+//Function to check if the author of a message can manage the server (needed to change the settings of the server)
+pub async fn can_manage_guild(ctx: &Context, msg: &Message) -> bool {
+    let guild_id = match msg.guild_id { //Only servers have permissions
+        Some(guild_id) => guild_id,
+        None => return false,
+    };
+    match (guild_id.to_partial_guild(&ctx.http).await, guild_id.member(&ctx.http, msg.author.id).await) { //Roles of the server and of the author
+        (Ok(guild), Ok(member)) => guild.member_permissions(&member).manage_guild(),
+        (Err(e), _) | (_, Err(e)) => {
+            println!("Error fetching permissions: {}", e); //Print error
+            false
+        }
+    }
+}
+
 //Function to send a simple embedded message
 pub async fn send_simple_format(ctx: &Context, msg: &Message, message: &str) {
     let embed = CreateMessage::new().embed(CreateEmbed::new() //Make Embedf
-        .color(0) //Set color to black
+        .color(theme::for_message(msg).color) //Set color to the theme
         .title("ScoreSaber Stats") //Set title
         .description(message) //Set description to message taken in
     );
@@ -23,7 +90,26 @@ pub async fn send_simple_format(ctx: &Context, msg: &Message, message: &str) {
     }
 }
 
+//Function to send an embed in the announcement channel of a guild (or the fallback channel if none is set)
+pub async fn send_announcement(ctx: &Context, guild_id: &str, fallback: Option<ChannelId>, embed: CreateEmbed) {
+    let channel = {
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        create_db(&conn).and_then(|_| fetch_announce_channel(&conn, guild_id)).unwrap_or(None)
+    };
+    let channel_id = match channel.and_then(|channel| channel.parse::<u64>().ok()) { //If the guild has a valid announcement channel
+        Some(channel) => ChannelId::new(channel),
+        None => match fallback {
+            Some(channel_id) => channel_id,
+            None => return, //Nowhere to announce
+        },
+    };
+    if let Err(why) = channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await { // If sending message has error
+        println!("Error sending announcement: {:?}", why); // Print error
+    }
+}
+
 //Function to act on message recieved
+#[allow(static_mut_refs, clippy::bool_comparison, clippy::redundant_pattern_matching, clippy::needless_borrow)] //Original command handling with the static flags
 pub async fn react_to_msg(ctx: Context, msg: Message){
     //Making variables
     let message = msg.content.as_str(); //Message to &str
@@ -33,6 +119,7 @@ pub async fn react_to_msg(ctx: Context, msg: Message){
     let message_word_count = message.split_whitespace().count(); //Count the number of words in the message
     //
     let mut cooldown:u64 = 300; //Default cooldown is 5 minutes
+    let mut session_gap = SESSION_GAP; //Minutes without a new score that end a session
     let mut player_id = ""; //Initiate player_id
     let mut working = true; //If stats does not have the correct word count
     //
@@ -42,6 +129,10 @@ pub async fn react_to_msg(ctx: Context, msg: Message){
         LINKED = false;
     }
 
+    let locale = locale::for_message(&msg); //Language and number format of the replies
+    let fields_error = locale.text("error.fields"); //Start of every word count error
+    let if_linked = locale.text("error.if_linked");
+
     //Check if player linked
     match discord_linked(&msg.author.name).await {
         Ok(Some(temp_linked_id)) => { //If player linked
@@ -59,20 +150,22 @@ pub async fn react_to_msg(ctx: Context, msg: Message){
     
 
     // Make sure message has right number of words
-    if message_word_count > 1 && command != "!track" && command != "!stats" && command != "!link" && command != "!help" && command != "!id" { //If the message has more than one word
+    if message_word_count > 1 && !matches!(command, "!track" | "!stats" | "!link" | "!help" | "!id" | "!leaderboard" | "!rival" | "!milestone" | "!goal" | "!theme" | "!fields" | "!card" | "!language" | "!country" | "!country-stats" | "!neighbours" | "!reach" | "!recommend" | "!snipe" | "!unplayed" | "!acc-profile" | "!mapboard" | "!streak" | "!calendar" | "!remind") { //If the message has more than one word
         if command == "!untrack"{ //If the command is untrack
-            send_simple_format(&ctx, &msg, "Incorrect number of fields. (`!untrack`)").await; //Send error
+            send_simple_format(&ctx, &msg, format!("{} (`!untrack`)", fields_error).as_str()).await; //Send error
         } else if command == "!unlink"{ //If the command is unlink
-            send_simple_format(&ctx, &msg, "Incorrect number of fields. (`!unlink`)").await; //Send error
+            send_simple_format(&ctx, &msg, format!("{} (`!unlink`)", fields_error).as_str()).await; //Send error
+        } else if command == "!setchannel"{ //If the command is setchannel
+            send_simple_format(&ctx, &msg, format!("{} (`!setchannel`)", fields_error).as_str()).await; //Send error
         } else {
-            send_simple_format(&ctx, &msg, "Incorrect number of fields.").await; //Send error
+            send_simple_format(&ctx, &msg, fields_error).await; //Send error
         }
         working = false; //Command link is not working
     } else if command == "!stats" {
         if message_word_count != 2 { //If command is stats and there is not two words in message
             unsafe{
                 if message_word_count != 1 || !LINKED {
-                    send_simple_format(&ctx, &msg, "Incorrect number of fields. (`!stats` `player_id`) (If linked use: `!stats`)").await; //Send error
+                    send_simple_format(&ctx, &msg, format!("{} (`!stats` `player_id`) ({} `!stats`)", fields_error, if_linked).as_str()).await; //Send error
                     working = false; //Command stats is not working
                 }
             }
@@ -82,19 +175,175 @@ pub async fn react_to_msg(ctx: Context, msg: Message){
         }
     } else if command == "!id" {
         if message_word_count != 2 { //If command is id and there is not two words in message
-            send_simple_format(&ctx, &msg, "Incorrect number of fields. (`!id` `player_name`)").await; //Send error
+            send_simple_format(&ctx, &msg, format!("{} (`!id` `player_name`)", fields_error).as_str()).await; //Send error
             working = false; //Command id is not working
         } else {
             player_id = first_param;
         }
+    } else if command == "!leaderboard" && message_word_count > 2 {
+        send_simple_format(&ctx, &msg, format!("{} (`!leaderboard` `{}|weeklyGain`)", fields_error, metrics::names()).as_str()).await; //Send error
+        working = false; //Command leaderboard is not working
+    } else if command == "!rival" && !matches!((first_param, message_word_count), ("add" | "remove", 3) | ("list", 2)) {
+        send_simple_format(&ctx, &msg, format!("{} (`!rival` `add|remove` `player_id`) (`!rival` `list`)", fields_error).as_str()).await; //Send error
+        working = false; //Command rival is not working
+    } else if command == "!milestone" && !matches!((first_param, message_word_count), ("add" | "remove", 4) | ("list", 2)) {
+        send_simple_format(&ctx, &msg, format!("{} (`!milestone` `add|remove` `{}` `value`) (`!milestone` `list`)", fields_error, metrics::names()).as_str()).await; //Send error
+        working = false; //Command milestone is not working
+    } else if command == "!goal" && !matches!((first_param, message_word_count), ("set", 4 | 5) | ("remove", 3) | ("list", 2)) {
+        send_simple_format(&ctx, &msg, format!("{} (`!goal` `set` `metric` `target` `YYYY-MM-DD [OPTIONAL]`) (`!goal` `remove` `metric`) (`!goal` `list`)", fields_error).as_str()).await; //Send error
+        working = false; //Command goal is not working
+    } else if command == "!theme" && message_word_count > 4 {
+        send_simple_format(&ctx, &msg, format!("{} (`!theme` `server [OPTIONAL]` `color|accent|layout|reset|show` `value`)", fields_error).as_str()).await; //Send error
+        working = false; //Command theme is not working
+    } else if command == "!country" && !matches!(message_word_count, 2 | 3) {
+        send_simple_format(&ctx, &msg, format!("{} (`!country` `CC` `page [OPTIONAL]`)", fields_error).as_str()).await; //Send error
+        working = false; //Command country is not working
+    } else if command == "!country-stats" && message_word_count != 2 {
+        send_simple_format(&ctx, &msg, format!("{} (`!country-stats` `CC`)", fields_error).as_str()).await; //Send error
+        working = false; //Command country-stats is not working
+    } else if command == "!neighbours" {
+        let scope = message.split_whitespace().next_back().filter(|scope| matches!(*scope, "global" | "country")); //Scope is always the last word
+        match (message_word_count, scope) {
+            (1, _) | (2, Some(_)) => unsafe { //Only the scope (or nothing) so use the linked account
+                if !LINKED {
+                    send_simple_format(&ctx, &msg, format!("{} (`!neighbours` `player_id` `global|country [OPTIONAL]`) ({} `!neighbours` `global|country [OPTIONAL]`)", fields_error, if_linked).as_str()).await; //Send error
+                    working = false; //Command neighbours is not working
+                }
+            },
+            (2, None) | (3, Some(_)) => {
+                player_id = first_param;
+                linking = false;
+            }
+            _ => {
+                send_simple_format(&ctx, &msg, format!("{} (`!neighbours` `player_id` `global|country [OPTIONAL]`) ({} `!neighbours` `global|country [OPTIONAL]`)", fields_error, if_linked).as_str()).await; //Send error
+                working = false; //Command neighbours is not working
+            }
+        }
+    } else if command == "!reach" {
+        let scoped = message.split_whitespace().next_back().is_some_and(|scope| matches!(scope, "global" | "country")); //Scope is always the last word
+        let word_count = message_word_count - scoped as usize; //Words without the scope
+        if word_count == 2 { //Only the rank so use the linked account
+            unsafe {
+                if !LINKED {
+                    send_simple_format(&ctx, &msg, format!("{} (`!reach` `player_id` `rank` `raw_pp [OPTIONAL]` `global|country [OPTIONAL]`) ({} `!reach` `rank` `raw_pp [OPTIONAL]`)", fields_error, if_linked).as_str()).await; //Send error
+                    working = false; //Command reach is not working
+                }
+            }
+        } else if matches!(word_count, 3 | 4) {
+            let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+            let rank_and_pp = word_count == 3 && unsafe { LINKED } && fetch_player_data_from_db(&conn, first_param).is_err(); //Linked accounts can give the rank and raw pp (first parameter is not a known player)
+            drop(conn); //Close database
+            if !rank_and_pp {
+                player_id = first_param;
+                linking = false;
+            }
+        } else {
+            send_simple_format(&ctx, &msg, format!("{} (`!reach` `player_id` `rank` `raw_pp [OPTIONAL]` `global|country [OPTIONAL]`) ({} `!reach` `rank` `raw_pp [OPTIONAL]`)", fields_error, if_linked).as_str()).await; //Send error
+            working = false; //Command reach is not working
+        }
+    } else if command == "!recommend" {
+        if message_word_count == 1 || recommend::is_option(first_param) { //Only options so use the linked account
+            unsafe {
+                if message_word_count > 3 || !LINKED {
+                    send_simple_format(&ctx, &msg, format!("{} (`!recommend` `player_id` `count [OPTIONAL]` `min-max stars [OPTIONAL]`) ({} `!recommend` `count [OPTIONAL]` `min-max stars [OPTIONAL]`)", fields_error, if_linked).as_str()).await; //Send error
+                    working = false; //Command recommend is not working
+                }
+            }
+        } else if message_word_count <= 4 {
+            player_id = first_param;
+            linking = false;
+        } else {
+            send_simple_format(&ctx, &msg, format!("{} (`!recommend` `player_id` `count [OPTIONAL]` `min-max stars [OPTIONAL]`)", fields_error).as_str()).await; //Send error
+            working = false; //Command recommend is not working
+        }
+    } else if command == "!snipe" {
+        if message_word_count == 2 { //Only the target so use the linked account
+            unsafe {
+                if !LINKED {
+                    send_simple_format(&ctx, &msg, format!("{} (`!snipe` `player_id` `target_id`) ({} `!snipe` `target_id`)", fields_error, if_linked).as_str()).await; //Send error
+                    working = false; //Command snipe is not working
+                }
+            }
+        } else if message_word_count == 3 {
+            player_id = first_param;
+            linking = false;
+        } else {
+            send_simple_format(&ctx, &msg, format!("{} (`!snipe` `player_id` `target_id`) ({} `!snipe` `target_id`)", fields_error, if_linked).as_str()).await; //Send error
+            working = false; //Command snipe is not working
+        }
+    } else if command == "!unplayed" {
+        if message_word_count == 2 { //Only the star range so use the linked account
+            unsafe {
+                if !LINKED {
+                    send_simple_format(&ctx, &msg, format!("{} (`!unplayed` `player_id` `min-max stars`) ({} `!unplayed` `min-max stars`)", fields_error, if_linked).as_str()).await; //Send error
+                    working = false; //Command unplayed is not working
+                }
+            }
+        } else if message_word_count == 3 {
+            player_id = first_param;
+            linking = false;
+        } else {
+            send_simple_format(&ctx, &msg, format!("{} (`!unplayed` `player_id` `min-max stars`) ({} `!unplayed` `min-max stars`)", fields_error, if_linked).as_str()).await; //Send error
+            working = false; //Command unplayed is not working
+        }
+    } else if command == "!acc-profile" {
+        if message_word_count != 2 { //If command is acc-profile and there is not two words in message
+            unsafe{
+                if message_word_count != 1 || !LINKED {
+                    send_simple_format(&ctx, &msg, format!("{} (`!acc-profile` `player_id`) ({} `!acc-profile`)", fields_error, if_linked).as_str()).await; //Send error
+                    working = false; //Command acc-profile is not working
+                }
+            }
+        } else {
+            player_id = first_param;
+            linking = false;
+        }
+    } else if command == "!mapboard" && message_word_count == 1 {
+        send_simple_format(&ctx, &msg, format!("{} (`!mapboard` `leaderboard_id|song` `score|acc [OPTIONAL]`)", fields_error).as_str()).await; //Send error
+        working = false; //Command mapboard is not working
+    } else if command == "!streak" || command == "!calendar" {
+        if message_word_count != 2 { //If there is not two words in message
+            unsafe{
+                if message_word_count != 1 || !LINKED {
+                    send_simple_format(&ctx, &msg, format!("{} (`{}` `player_id`) ({} `{}`)", fields_error, command, if_linked, command).as_str()).await; //Send error
+                    working = false; //Command streak or calendar is not working
+                }
+            }
+        } else {
+            player_id = first_param;
+            linking = false;
+        }
+    } else if command == "!remind" && !matches!((first_param, message_word_count), ("on", 2 | 3) | ("off", 2)) {
+        send_simple_format(&ctx, &msg, format!("{} (`!remind` `on` `days [OPTIONAL]`) (`!remind` `off`)", fields_error).as_str()).await; //Send error
+        working = false; //Command remind is not working
+    } else if command == "!language" && message_word_count > 3 {
+        send_simple_format(&ctx, &msg, format!("{} (`!language` `server [OPTIONAL]` `en|de|es|reset|show`)", fields_error).as_str()).await; //Send error
+        working = false; //Command language is not working
+    } else if command == "!fields" && !matches!((first_param, message_word_count), ("set", 3..) | ("reset" | "show", 2) | ("", 1)) {
+        send_simple_format(&ctx, &msg, format!("{} (`!fields` `set` `{}...`) (`!fields` `reset|show`)", fields_error, metrics::names()).as_str()).await; //Send error
+        working = false; //Command fields is not working
+    } else if command == "!card" {
+        if message_word_count != 2 { //If command is card and there is not two words in message
+            unsafe{
+                if message_word_count != 1 || !LINKED {
+                    send_simple_format(&ctx, &msg, format!("{} (`!card` `player_id`) ({} `!card`)", fields_error, if_linked).as_str()).await; //Send error
+                    working = false; //Command card is not working
+                }
+            }
+        } else {
+            player_id = first_param;
+            linking = false;
+        }
     } else if command == "!link" && message_word_count !=2 {
-        send_simple_format(&ctx, &msg, "Incorrect number of fields. (`!link` `player_id`)").await; //Send error
+        send_simple_format(&ctx, &msg, format!("{} (`!link` `player_id`)", fields_error).as_str()).await; //Send error
         working = false; //Command link is not working
     } else if command == "!track"{ //If command is track
-        if message_word_count != 3 { //If there is not three words in message
+        let gap_param = message.split_whitespace().skip(2).last().filter(|word| word.ends_with('m')); //Session gap is the last word (like 30m)
+        let track_word_count = message_word_count - gap_param.is_some() as usize; //Words without the session gap
+        if track_word_count != 3 { //If there is not three words in message
             unsafe {
-                if message_word_count !=2 || !LINKED { //If command is not linked
-                    send_simple_format(&ctx, &msg, "Incorrect number of fields. (`!track` `player_id` `time (seconds)`) (If linked use: `!track` `time`)").await; //Send error
+                if track_word_count !=2 || !LINKED { //If command is not linked
+                    send_simple_format(&ctx, &msg, format!("{} (`!track` `player_id` `time (seconds)` `session gap (minutes) [OPTIONAL]`) ({} `!track` `time`)", fields_error, if_linked).as_str()).await; //Send error
                     working = false; //Command stats is not working
                 } else {
                     match first_param.parse::<u64>() { //Try to parse cooldown from message
@@ -102,12 +351,12 @@ pub async fn react_to_msg(ctx: Context, msg: Message){
                             if num > 5{ //If number is greater than 5
                                 cooldown = num; //Set cooldown
                             }  else {
-                                send_simple_format(&ctx, &msg, "Please use a number over 5 seconds. (`!track` `player_id` `time (seconds)`)").await; //Send error
+                                send_simple_format(&ctx, &msg, format!("{} (`!track` `player_id` `time (seconds)` `session gap (minutes) [OPTIONAL]`)", locale.text("track.min")).as_str()).await; //Send error
                                 working = false; //Command track is not working
                             }
                         }
                         Err(e) => { //If unable to parse
-                            send_simple_format(&ctx, &msg, "Please use a number for **time**. Make sure it is positive. (`!track` `player_id` `time (seconds)`)").await; //Send error
+                            send_simple_format(&ctx, &msg, format!("{} (`!track` `player_id` `time (seconds)` `session gap (minutes) [OPTIONAL]`)", locale.text("track.nan")).as_str()).await; //Send error
                             println!("Error parsing cooldown: {:?}", e); //Print error
                             working = false; //Command track is not working
                         }
@@ -122,17 +371,28 @@ pub async fn react_to_msg(ctx: Context, msg: Message){
                         player_id = first_param;
                         linking = false;
                     }  else {
-                        send_simple_format(&ctx, &msg, "Please use a number over 5 seconds. (`!track` `player_id` `time (seconds)`)").await; //Send error
+                        send_simple_format(&ctx, &msg, format!("{} (`!track` `player_id` `time (seconds)` `session gap (minutes) [OPTIONAL]`)", locale.text("track.min")).as_str()).await; //Send error
                         working = false; //Command track is not working
                     }
                 }
                 Err(e) => { //If unable to parse
-                    send_simple_format(&ctx, &msg, "Please use a number for **time**. Make sure it is positive. (`!track` `player_id` `time (seconds)`)").await; //Send error
+                    send_simple_format(&ctx, &msg, format!("{} (`!track` `player_id` `time (seconds)` `session gap (minutes) [OPTIONAL]`)", locale.text("track.nan")).as_str()).await; //Send error
                     println!("Error parsing cooldown: {:?}", e); //Print error
                     working = false; //Command track is not working
                 }
             }
         }
+        if working {
+            if let Some(gap) = gap_param { //If a session gap was given
+                match gap.trim_end_matches('m').parse::<i64>() { //Try to parse the minutes
+                    Ok(minutes) if (1..=MAX_SESSION_GAP).contains(&minutes) => session_gap = minutes,
+                    _ => {
+                        send_simple_format(&ctx, &msg, format!("{} (`!track` `player_id` `time (seconds)` `session gap (minutes) [OPTIONAL]`)", locale.fill("track.gap", &[&MAX_SESSION_GAP.to_string()])).as_str()).await; //Send error
+                        working = false; //Command track is not working
+                    }
+                }
+            }
+        }
     }
 
     match command { //Ract to command
@@ -143,8 +403,8 @@ pub async fn react_to_msg(ctx: Context, msg: Message){
                         player_id = linked_id.as_str();
                     }
                 }
-                if !send_stats(player_id, ctx.clone(), msg.clone()).await{ //Function to send stats
-                    send_simple_format(&ctx, &msg, "Invalid player ID provided.").await; //Send error
+                if !send_stats(player_id, ctx.clone(), msg.clone(), false).await{ //Function to send stats
+                    send_simple_format(&ctx, &msg, locale.text("error.invalid_id")).await; //Send error
                 }
                 unsafe{ //Using pub static variables
                     COUNT += 1; // Increment the success count
@@ -157,6 +417,7 @@ pub async fn react_to_msg(ctx: Context, msg: Message){
                 TRACK = true; //Keep looping because it is tracking
                 TRACKING = true; //Bot is currently tracking
             }
+            let mut tracker: Option<SessionTracker> = None; //Follows the recent scores for play sessions
             for loop_count in 0.. { //Loop
                 unsafe{ //Using pub static variables
                     if TRACK == false { //If the function will only run once
@@ -169,14 +430,30 @@ pub async fn react_to_msg(ctx: Context, msg: Message){
                         player_id = linked_id.as_str();
                     }
                 }
-                if loop_count == 0{send_simple_format(&ctx, &msg, format!("Began tracking every {} seconds!", cooldown).as_str()).await} //If it is looping for the same time then print
-                if !send_stats(player_id, ctx.clone(), msg.clone()).await{ //Function to send stats
-                    send_simple_format(&ctx, &msg, "****FAILED:**** Invalid player ID provided.").await; //Send error
-                    break; //Exit the loop
+                if loop_count == 0 { //First loop sends the stats, after that only session recaps are sent
+                    send_simple_format(&ctx, &msg, locale.fill("track.began", &[&cooldown.to_string()]).as_str()).await;
+                    if !send_stats(player_id, ctx.clone(), msg.clone(), false).await{ //Function to send stats
+                        send_simple_format(&ctx, &msg, format!("**{}** {}", locale.text("track.failed"), locale.text("error.invalid_id")).as_str()).await; //Send error
+                        break; //Exit the loop
+                    }
+                    unsafe{ //Using pub static variables
+                        COUNT += 1; // Increment the success count
+                        println!("Stats message sent successfully to Discord [Message Count: {}] (!track [Every {} seconds])", COUNT, cooldown); // Prints if successful
+                    }
                 }
-                unsafe{ //Using pub static variables
-                    COUNT += 1; // Increment the success count
-                    println!("Stats message sent successfully to Discord [Message Count: {}] (!track [Every {} seconds])", COUNT, cooldown); // Prints if successful
+                match tracker.as_mut() {
+                    Some(session_tracker) => match session_tracker.poll(player_id).await { //Check for new scores
+                        Ok(Some(session)) => { //Player stopped playing
+                            send_recap(&ctx, &msg, session_tracker, &session).await; //Function to send the session recap
+                            send_stats(player_id, ctx.clone(), msg.clone(), true).await; //Stats of the whole session (only if the picked stats changed)
+                        }
+                        Ok(None) => {} //Not playing or still playing
+                        Err(e) => println!("Error fetching recent scores from API: {}", e), //Try again next loop
+                    },
+                    None => match SessionTracker::start(player_id, session_gap).await { //Start following the recent scores
+                        Ok(session_tracker) => tracker = Some(session_tracker),
+                        Err(e) => println!("Error fetching recent scores from API: {}", e), //Try again next loop
+                    },
                 }
                 sleep(Duration::from_secs(cooldown)).await; //Waits before looping
             }
@@ -189,10 +466,10 @@ pub async fn react_to_msg(ctx: Context, msg: Message){
                 unsafe{ //Using pub static variables
                     if TRACKING{ //If bot is tracking
                         TRACK = false; //Stop looping in track
-                        send_simple_format(&ctx, &msg, "Tracking has stopped!").await; //Send message
+                        send_simple_format(&ctx, &msg, locale.text("untrack.stopped")).await; //Send message
                         println!("Tracking has stopped (!untrack)"); // Prints if successful
                     } else {
-                        send_simple_format(&ctx, &msg, "Not currently tracking!").await; //Send error
+                        send_simple_format(&ctx, &msg, locale.text("untrack.not_tracking")).await; //Send error
                     }
                 }
             }
@@ -207,24 +484,24 @@ pub async fn react_to_msg(ctx: Context, msg: Message){
                     if let Ok(linked) = link_discord(&msg.author.name, &player_id).await { //Function to create database if not exists
                         if linked {
                             println!("\nAccount: `{}` has been linked with the discord: `{}` (!link)\n", player_id, msg.author.name); //Print success
-                            send_simple_format(&ctx, &msg, format!("Account: `{}` has been linked with `{}` (discord)", player_id, msg.author.name).as_str()).await; //If worked
+                            send_simple_format(&ctx, &msg, locale.fill("link.linked", &[player_id, &msg.author.name]).as_str()).await; //If worked
                         } else { //If not linked
-                            send_simple_format(&ctx, &msg, "Invalid ID provided! (`!link` `player_id`)").await; //Send error
+                            send_simple_format(&ctx, &msg, format!("{} (`!link` `player_id`)", locale.text("link.invalid")).as_str()).await; //Send error
                         }
                     } else {
-                        send_simple_format(&ctx, &msg, format!("Please generate an account in the database for `{}` by running a stats command! (`!stats` `player_id`)", player_id).as_str()).await; //If invalid
+                        send_simple_format(&ctx, &msg, format!("{} (`!stats` `player_id`)", locale.fill("link.no_account", &[player_id])).as_str()).await; //If invalid
                     }
                 } else { //If failed to remove previous discord
-                    send_simple_format(&ctx, &msg, "Failed to remove previous Discord link. Please try again.").await; //Send error
+                    send_simple_format(&ctx, &msg, locale.text("link.remove_failed")).await; //Send error
                 }
             }
         }
         "!unlink" => {
             if working {
                 if let Ok(_) = delete_discord_if_exists(&msg.author.name).await { //If deleted discord link
-                    send_simple_format(&ctx, &msg, format!("\nDiscord `{}` has been removed if it existed (!link)", msg.author.name).as_str()).await; //Send success
+                    send_simple_format(&ctx, &msg, locale.fill("unlink.done", &[&msg.author.name]).as_str()).await; //Send success
                 } else { //If failed to remove discord link
-                    send_simple_format(&ctx, &msg, "Failed to remove previous Discord link. Please try again.").await; //Send error
+                    send_simple_format(&ctx, &msg, locale.text("link.remove_failed")).await; //Send error
                 }
             }
         }
@@ -239,12 +516,12 @@ pub async fn react_to_msg(ctx: Context, msg: Message){
                             if let Some(player) = data.players.get(player_counter) { //If player exists
                                 if player.name.to_lowercase() == player_id.to_lowercase() { //If player name found matches player name requested
                                     let found_player_id = &player.id; //Find id of player
-                                    send_simple_format(&ctx, &msg, format!("The player id of {} is `{}`", player_id, found_player_id).as_str()).await; //Send player_id
+                                    send_simple_format(&ctx, &msg, locale.fill("id.found", &[player_id, found_player_id]).as_str()).await; //Send player_id
                                     println!("The player id of {} is `{}` (!id)", player_id, found_player_id); //Print result
                                     exit_loop = true; //Exit loop
                                 }
                             } else { //If player is not found
-                                send_simple_format(&ctx, &msg, format!("No players matched the name `{}`!\nDid you spell it right? (Caps don't affect search)", player_id).as_str()).await; //Send error
+                                send_simple_format(&ctx, &msg, locale.fill("id.none", &[player_id]).as_str()).await; //Send error
                                 println!("No players matched the name `{}`! (!id)", player_id); //Print error
                                 exit_loop = true; //Exit loop
                             }
@@ -252,26 +529,207 @@ pub async fn react_to_msg(ctx: Context, msg: Message){
                         }
                     }
                     Err(e) => { //If failed to search player
-                        send_simple_format(&ctx, &msg, format!("Error searching for and fetching player data from API: {}", e).as_str()).await; //Send error
+                        send_simple_format(&ctx, &msg, locale.fill("id.error", &[&e.to_string()]).as_str()).await; //Send error
                         println!("Error fetching data from API: {}", e); //Print error
                     }
                 }
             }
         }
+        "!leaderboard" => {
+            if working {
+                send_leaderboard(&ctx, &msg, first_param).await; //Function to send the guild leaderboard
+            }
+        }
+        "!rival" => {
+            if working {
+                rival_command(&ctx, &msg, first_param, message_parts.next().unwrap_or(""), &linked_id).await; //Function to add, remove or list rivals
+            }
+        }
+        "!milestone" => {
+            if working {
+                let metric = message_parts.next().unwrap_or(""); //Metric of the milestone
+                let value = message_parts.next().unwrap_or(""); //Value of the milestone
+                milestone_command(&ctx, &msg, first_param, metric, value).await; //Function to add, remove or list milestones
+            }
+        }
+        "!goal" => {
+            if working {
+                let parts: Vec<&str> = message.split_whitespace().skip(1).collect(); //Parameters of the command
+                goal_command(&ctx, &msg, &parts, &linked_id).await; //Function to set, remove or list goals
+            }
+        }
+        "!theme" => {
+            if working {
+                let parts: Vec<&str> = message.split_whitespace().skip(1).collect(); //Parameters of the command
+                theme_command(&ctx, &msg, &parts).await; //Function to set, reset or show the theme
+            }
+        }
+        "!fields" => {
+            if working {
+                let parts: Vec<&str> = message.split_whitespace().skip(1).collect(); //Parameters of the command
+                fields_command(&ctx, &msg, &parts).await; //Function to set, reset or show the picked stats
+            }
+        }
+        "!card" => {
+            if working {
+                unsafe {
+                    if LINKED && linking{
+                        player_id = linked_id.as_str();
+                    }
+                }
+                send_card(&ctx, &msg, player_id).await; //Function to render and send the profile card
+            }
+        }
+        "!country" => {
+            if working {
+                send_country_leaderboard(&ctx, &msg, first_param, message_parts.next().unwrap_or("")).await; //Function to send the top players of a country
+            }
+        }
+        "!country-stats" => {
+            if working {
+                send_country_stats(&ctx, &msg, first_param).await; //Function to send stats of the tracked players of a country
+            }
+        }
+        "!neighbours" => {
+            if working {
+                unsafe {
+                    if LINKED && linking{
+                        player_id = linked_id.as_str();
+                    }
+                }
+                let scope = message.split_whitespace().next_back().filter(|scope| matches!(*scope, "global" | "country")).unwrap_or("global"); //Global unless country is asked for
+                send_neighbours(&ctx, &msg, player_id, scope).await; //Function to send the players ranked around the player
+            }
+        }
+        "!reach" => {
+            if working {
+                let scope = message.split_whitespace().next_back().filter(|scope| matches!(*scope, "global" | "country")).unwrap_or("global"); //Global unless country is asked for
+                let mut parts = message_parts.filter(|part| !matches!(*part, "global" | "country")); //Parameters after the first one without the scope
+                let mut rank = first_param; //Rank is the first parameter if linked
+                unsafe {
+                    if LINKED && linking{
+                        player_id = linked_id.as_str();
+                    } else {
+                        rank = parts.next().unwrap_or(""); //Otherwise it comes after the player
+                    }
+                }
+                send_reach(&ctx, &msg, player_id, rank, parts.next().unwrap_or(""), scope).await; //Function to send the pp needed to reach a rank
+            }
+        }
+        "!recommend" => {
+            if working {
+                let mut options: Vec<&str> = message.split_whitespace().skip(1).collect(); //Parameters of the command
+                unsafe {
+                    if LINKED && linking{
+                        player_id = linked_id.as_str();
+                    } else {
+                        options.remove(0); //First parameter is the player
+                    }
+                }
+                send_recommendations(&ctx, &msg, player_id, &options).await; //Function to send the maps with the biggest expected pp gain
+            }
+        }
+        "!snipe" => {
+            if working {
+                let mut target_id = first_param; //Target is the first parameter if linked
+                unsafe {
+                    if LINKED && linking{
+                        player_id = linked_id.as_str();
+                    } else {
+                        target_id = message_parts.next().unwrap_or(""); //Otherwise it comes after the player
+                    }
+                }
+                send_snipes(&ctx, &msg, player_id, target_id).await; //Function to send the maps to snipe from the target
+            }
+        }
+        "!unplayed" => {
+            if working {
+                let mut range = first_param; //Star range is the first parameter if linked
+                unsafe {
+                    if LINKED && linking{
+                        player_id = linked_id.as_str();
+                    } else {
+                        range = message_parts.next().unwrap_or(""); //Otherwise it comes after the player
+                    }
+                }
+                send_unplayed(&ctx, &msg, player_id, range).await; //Function to send the unplayed maps in the star range
+            }
+        }
+        "!acc-profile" => {
+            if working {
+                unsafe {
+                    if LINKED && linking{
+                        player_id = linked_id.as_str();
+                    }
+                }
+                send_acc_profile(&ctx, &msg, player_id).await; //Function to send the accuracy by star rating
+            }
+        }
+        "!mapboard" => {
+            if working {
+                let params: Vec<&str> = message.split_whitespace().skip(1).collect(); //Song names can have spaces
+                send_mapboard(&ctx, &msg, &params).await; //Function to send the scores of the linked players on a map
+            }
+        }
+        "!streak" | "!calendar" => {
+            if working {
+                unsafe {
+                    if LINKED && linking{
+                        player_id = linked_id.as_str();
+                    }
+                }
+                if command == "!streak" {
+                    send_streak(&ctx, &msg, player_id).await; //Function to send the play streaks
+                } else {
+                    send_calendar(&ctx, &msg, player_id).await; //Function to render and send the play calendar
+                }
+            }
+        }
+        "!remind" => {
+            if working {
+                let parts: Vec<&str> = message.split_whitespace().skip(1).collect(); //Parameters of the command
+                remind_command(&ctx, &msg, &parts, &linked_id).await; //Function to turn inactivity reminders on or off
+            }
+        }
+        "!language" => {
+            if working {
+                let parts: Vec<&str> = message.split_whitespace().skip(1).collect(); //Parameters of the command
+                language_command(&ctx, &msg, &parts).await; //Function to set, reset or show the language
+            }
+        }
+        "!setchannel" => {
+            if working {
+                if msg.guild_id.is_some() && !can_manage_guild(&ctx, &msg).await { //Only managers can move the announcements
+                    send_simple_format(&ctx, &msg, locale.text("error.manage_guild")).await; //Send error
+                } else if let Some(guild_id) = msg.guild_id { //If message was sent in a server
+                    let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+                    let result = create_db(&conn).and_then(|_| set_announce_channel(&conn, &guild_id.to_string(), &msg.channel_id.to_string(), week_start(current_time())));
+                    drop(conn); //Close database before sending
+                    if let Err(e) = result { //If failed to store the channel
+                        println!("Error setting announcement channel: {}", e); //Print error
+                        send_simple_format(&ctx, &msg, locale.text("setchannel.failed")).await; //Send error
+                    } else {
+                        println!("Announcement channel set to `{}` (!setchannel)", msg.channel_id); //Print success
+                        send_simple_format(&ctx, &msg, locale.text("setchannel.done")).await; //Send success
+                    }
+                } else {
+                    send_simple_format(&ctx, &msg, locale.text("setchannel.server_only")).await; //Send error
+                }
+            }
+        }
         "!help" => {
             if working {
+                let mut help = String::new();
+                for (name, usage) in HELP { //One line per command
+                    help += &format!("**{}**: {} - {}\n\n", name, usage, locale.text(&format!("help.{}", name.to_lowercase())));
+                    if name == "GOAL" { //Stat names go right after the commands that use them
+                        help += &format!("**{}**: {}\n\n", locale.text("help.stat_names"), metrics::names().replace('|', ", "));
+                    }
+                }
                 let embed = CreateMessage::new().embed(CreateEmbed::new() //Make Embed
-                    .color(0) //Set color to black
-                    .title("ScoreSaber Stats - Help") //Set title
-                    .description("
-                        **HELP**: `!help` - Provides list of commands\n
-                        **STATS**: `!stats + player_id [NOTLINKED]` - Provides player statistics\n
-                        **TRACK**: `!track + player_id [NOTLINKED] + time` - Provides player statistics repeatedly based on time (seconds)\n
-                        **UNTRACK**: `!untrack` - Stops tracking\n
-                        **LINK**: `!link + player_id` - Links player_id to messenger's discord\n
-                        **UNLINK**: `!unlink` - Deletes link to messenger's discord\n
-                        **ID**: `!id + player_name` - Find a player_id by name\n
-                    ") //Set description to message taken in
+                    .color(theme::for_message(&msg).color) //Set color to the theme
+                    .title(locale.text("help.title")) //Set title
+                    .description(help) //Set description to the commands
                 );
                 if let Err(why) = msg.channel_id.send_message(&ctx.http, embed).await { // If sending message has error
                     println!("Error sending message: {:?}", why); // Print error
@@ -279,7 +737,7 @@ pub async fn react_to_msg(ctx: Context, msg: Message){
             }
         }
         _ => { //If command is unknown
-            send_simple_format(&ctx, &msg, "Invalid Command").await; //Send error
+            send_simple_format(&ctx, &msg, locale.text("error.invalid_command")).await; //Send error
         }
     }
 }
\ No newline at end of file
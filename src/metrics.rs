@@ -0,0 +1,121 @@
+use crate::Snapshot; //Snapshot struct
+use crate::locale::Locale; //Language and number format
+
+//Kind of number a metric holds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Integer,
+    Decimal,
+}
+
+//A stat that can be compared, ranked and formatted
+#[derive(Debug)]
+pub struct Metric {
+    pub name: &'static str, //Name used in commands and in the database
+    pub label: &'static str, //Name shown in embeds
+    pub kind: Kind,
+    pub higher_is_better: bool, //False for ranks (a lower rank is better)
+    pub value: fn(&Snapshot) -> f64, //Takes the value out of the stats
+    pub format: fn(f64, Locale) -> String, //Formats a value (without units)
+    pub unit: &'static str, //Shown after the value
+}
+
+//Every metric (adding a metric only needs a new line here)
+pub const METRICS: [Metric; 9] = [
+    Metric { name: "pp", label: "Performance Points (PP)", kind: Kind::Decimal, higher_is_better: true, value: |s| s.pp, format: |v, locale| locale.decimal(v, 2), unit: "pp" },
+    Metric { name: "rank", label: "Global Rank", kind: Kind::Integer, higher_is_better: false, value: |s| s.rank as f64, format: format_integer, unit: "" },
+    Metric { name: "countryRank", label: "Country Rank", kind: Kind::Integer, higher_is_better: false, value: |s| s.countryRank as f64, format: format_integer, unit: "" },
+    Metric { name: "acc", label: "Average Ranked Accuracy", kind: Kind::Decimal, higher_is_better: true, value: |s| s.averageRankedAccuracy, format: |v, locale| locale.decimal(v, 4), unit: "%" },
+    Metric { name: "totalScore", label: "Total Score", kind: Kind::Integer, higher_is_better: true, value: |s| s.totalScore as f64, format: format_integer, unit: "" },
+    Metric { name: "rankedScore", label: "Total Ranked Score", kind: Kind::Integer, higher_is_better: true, value: |s| s.totalRankedScore as f64, format: format_integer, unit: "" },
+    Metric { name: "totalPlays", label: "Total Play Count", kind: Kind::Integer, higher_is_better: true, value: |s| s.totalPlayCount as f64, format: format_integer, unit: " plays" },
+    Metric { name: "rankedPlays", label: "Ranked Play Count", kind: Kind::Integer, higher_is_better: true, value: |s| s.rankedPlayCount as f64, format: format_integer, unit: " plays" },
+    Metric { name: "replays", label: "Replays Watched", kind: Kind::Integer, higher_is_better: true, value: |s| s.replaysWatched as f64, format: format_integer, unit: "" },
+];
+
+//Function to format integers with grouped digits
+fn format_integer(value: f64, locale: Locale) -> String {
+    locale.integer(value as i64, false)
+}
+
+//Function to find a metric by name (caps don't matter)
+pub fn find(name: &str) -> Option<&'static Metric> {
+    METRICS.iter().find(|metric| metric.name.eq_ignore_ascii_case(name))
+}
+
+//Function to list the names of every metric (used in help and error messages)
+pub fn names() -> String {
+    METRICS.iter().map(|metric| metric.name).collect::<Vec<&str>>().join("|")
+}
+
+impl Metric {
+    //Function to format a value with its unit
+    pub fn display(&self, value: f64, locale: Locale) -> String {
+        format!("{}{}", (self.format)(value, locale), locale.unit(self.unit))
+    }
+
+    //Function to check if a value changed at the precision it is shown with (ignores float noise)
+    pub fn changed(&self, old: f64, new: f64) -> bool {
+        match self.kind {
+            Kind::Integer => old != new,
+            Kind::Decimal => (self.format)(old, Locale::En) != (self.format)(new, Locale::En),
+        }
+    }
+
+    //Function to format a change by whether it is good or bad for the player (▲ is always an improvement)
+    pub fn format_change(&self, old: f64, new: f64, locale: Locale) -> String {
+        self.format_improvement(self.improvement(old, new), locale)
+    }
+
+    //Function to format an improvement (negative if it got worse) with an arrow (= if nothing changed at the shown precision)
+    pub fn format_improvement(&self, improvement: f64, locale: Locale) -> String {
+        let arrow = if !self.changed(0.0, improvement) { "=" } else if improvement > 0.0 { "▲" } else { "▼" };
+        format!("{} {}", arrow, self.display(improvement.abs(), locale))
+    }
+
+    //Function to check if a value is ranked (ranks of 0 mean the player is inactive)
+    pub fn is_ranked(&self, value: f64) -> bool {
+        self.higher_is_better || value > 0.0
+    }
+
+    //Function to find how much a value improved (negative if it got worse)
+    pub fn improvement(&self, old: f64, new: f64) -> f64 {
+        if self.higher_is_better { new - old } else { old - new }
+    }
+
+    //Function to check if a value is at or past a target
+    pub fn reached(&self, value: f64, target: f64) -> bool {
+        if self.higher_is_better {
+            value >= target
+        } else {
+            self.is_ranked(value) && value <= target
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_ignores_noise_below_the_shown_precision() {
+        let pp = find("pp").unwrap();
+        assert!(pp.changed(100.0, 100.01));
+        assert!(!pp.changed(100.0, 100.0001));
+        let rank = find("rank").unwrap();
+        assert!(rank.changed(10.0, 11.0));
+        assert!(!rank.changed(10.0, 10.0));
+    }
+
+    #[test]
+    fn format_change_points_the_arrow_at_improvements() {
+        let pp = find("pp").unwrap();
+        assert_eq!(pp.format_change(100.0, 112.5, Locale::En), "▲ 12.50pp");
+        assert_eq!(pp.format_change(112.5, 100.0, Locale::En), "▼ 12.50pp");
+        assert_eq!(pp.format_change(100.0, 100.0001, Locale::En), "= 0.00pp");
+        let rank = find("rank").unwrap(); //Lower rank is better
+        assert_eq!(rank.format_change(1500.0, 1200.0, Locale::En), "▲ 300");
+        assert_eq!(rank.format_change(1200.0, 1500.0, Locale::De), "▼ 300");
+        assert_eq!(rank.format_change(1200.0, 1200.0, Locale::En), "= 0");
+    }
+}
@@ -0,0 +1,175 @@
+use crate::PlayerData; //PlayerData struct
+use crate::Changes; //Changes struct
+use crate::imports::*; //Imports
+use crate::datatweaks::{add_milestone_threshold, create_db, fetch_linked_discord, fetch_milestone_thresholds, record_milestone, remove_milestone_threshold};
+use crate::metrics::{self, Metric};
+use crate::theme; //Embed themes
+use crate::locale::{self, Locale};
+use crate::message::{can_manage_guild, send_announcement, send_simple_format};
+
+//Default milestones every guild gets (metric, values)
+const DEFAULT_MILESTONES: [(&str, &[f64]); 4] = [
+    ("rank", &[100_000.0, 50_000.0, 25_000.0, 10_000.0, 5_000.0, 2_500.0, 1_000.0, 500.0, 100.0, 50.0, 10.0, 1.0]),
+    ("pp", &[1_000.0, 2_000.0, 3_000.0, 4_000.0, 5_000.0, 6_000.0, 7_000.0, 8_000.0, 9_000.0, 10_000.0, 11_000.0, 12_000.0, 13_000.0, 14_000.0, 15_000.0, 16_000.0, 17_000.0, 18_000.0, 19_000.0, 20_000.0]),
+    ("rankedPlays", &[100.0, 250.0, 500.0, 1_000.0, 2_000.0, 5_000.0, 10_000.0]),
+    ("acc", &[80.0, 85.0, 90.0, 92.5, 95.0, 97.5]),
+];
+
+//Function to describe a milestone
+fn describe(metric: &Metric, value: f64, locale: Locale) -> String {
+    if metric.higher_is_better {
+        format!("{} {}", metric.display(value, locale), locale.label(metric))
+    } else {
+        locale.fill("milestone.top", &[&(metric.format)(value, locale), locale.label(metric)])
+    }
+}
+
+//Function to check if a change crossed a milestone (coming back from unranked doesn't count)
+fn crossed(metric: &Metric, old: f64, new: f64, value: f64) -> bool {
+    metric.is_ranked(old) && !metric.reached(old, value) && metric.reached(new, value)
+}
+
+//Function to find the milestones a linked player just crossed (records them so they are never announced twice)
+fn find_milestones(data: &PlayerData, changes: &Changes, guild_id: Option<&str>, locale: Locale) -> Result<Vec<String>> {
+    let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+    create_db(&conn)?; //Create database if not exists
+    if fetch_linked_discord(&conn, &data.id)?.is_none() { //Milestones are only for linked players
+        return Ok(Vec::new());
+    }
+
+    //Default milestones plus the ones configured by the guild
+    let mut thresholds: Vec<(String, f64)> = DEFAULT_MILESTONES.iter()
+        .flat_map(|(metric, values)| values.iter().map(move |value| (metric.to_string(), *value)))
+        .collect();
+    if let Some(guild_id) = guild_id {
+        thresholds.extend(fetch_milestone_thresholds(&conn, guild_id)?);
+    }
+
+    let mut reached = Vec::new();
+    for (name, value) in thresholds { //Loop through every milestone
+        if let Some(change) = changes.get(&name) { //If the metric changed
+            if crossed(change.metric, change.old, change.new, value) && record_milestone(&conn, &data.id, &name, value)? { //If crossed for the first time
+                reached.push(describe(change.metric, value, locale));
+            }
+        }
+    }
+    Ok(reached)
+}
+
+//Function to announce every milestone a player just reached
+pub async fn announce_milestones(ctx: &Context, msg: &Message, data: &PlayerData, changes: &Changes) {
+    let guild_id = msg.guild_id.map(|guild_id| guild_id.to_string());
+    let locale = locale::for_message(msg); //Language and number format
+    let reached = match find_milestones(data, changes, guild_id.as_deref(), locale) {
+        Ok(reached) => reached,
+        Err(e) => {
+            println!("Error checking milestones: {}", e); //Print error
+            return;
+        }
+    };
+    if reached.is_empty() { //Nothing to announce
+        return;
+    }
+    let embed = CreateEmbed::new() //Make Embed
+        .color(theme::for_message(msg).accent) //Set color to the accent of the theme
+        .title(locale.text("milestone.title")) //Set title
+        .description(format!("{}\n{}", locale.fill("milestone.reached", &[&data.name]), reached.iter().map(|milestone| format!("- **{}**", milestone)).collect::<Vec<String>>().join("\n")));
+    match guild_id {
+        Some(guild_id) => send_announcement(ctx, &guild_id, Some(msg.channel_id), embed).await,
+        None => { //If refreshed outside of a server then send it right here
+            if let Err(why) = msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await { // If sending message has error
+                println!("Error sending message: {:?}", why); // Print error
+            }
+        }
+    }
+    println!("{} reached milestones: {}", data.name, reached.join(", ")); //Print milestones
+}
+
+//Function to react to the milestone command (add, remove, list)
+pub async fn milestone_command(ctx: &Context, msg: &Message, action: &str, metric: &str, value: &str) {
+    let locale = locale::for_message(msg); //Language and number format
+    let guild_id = match msg.guild_id { //Milestones are configured per server
+        Some(guild_id) => guild_id.to_string(),
+        None => {
+            send_simple_format(ctx, msg, locale.text("milestone.guild_only")).await; //Send error
+            return;
+        }
+    };
+    if action == "list" {
+        let custom = {
+            let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+            create_db(&conn).and_then(|_| fetch_milestone_thresholds(&conn, &guild_id)).unwrap_or_default()
+        };
+        let mut description = format!("**{}**\n", locale.text("milestone.defaults"));
+        for (metric, values) in DEFAULT_MILESTONES { //Loop through the defaults
+            description += &format!("{}: {}\n", metric, values.iter().map(|value| value.to_string()).collect::<Vec<String>>().join(", "));
+        }
+        description += &format!("\n**{}**\n", locale.text("milestone.server"));
+        if custom.is_empty() {
+            description += &format!("{} (`!milestone` `add` `metric` `value`)", locale.text("milestone.none"));
+        }
+        for (name, value) in custom { //Loop through the guild's milestones
+            if let Some(metric) = metrics::find(&name) {
+                description += &format!("- {}\n", describe(metric, value, locale));
+            }
+        }
+        send_simple_format(ctx, msg, &description).await; //Send list
+        return;
+    }
+
+    if !can_manage_guild(ctx, msg).await { //Only managers can change the milestones of the server
+        send_simple_format(ctx, msg, locale.text("error.manage_guild")).await; //Send error
+        return;
+    }
+    let (metric, value) = match (metrics::find(metric), value.parse::<f64>()) { //Parse the milestone
+        (Some(metric), Ok(value)) if value > 0.0 => (metric, value),
+        _ => {
+            send_simple_format(ctx, msg, locale.fill("milestone.invalid", &[&metrics::names()]).as_str()).await; //Send error
+            return;
+        }
+    };
+    let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+    let result = create_db(&conn).and_then(|_| {
+        if action == "add" {
+            add_milestone_threshold(&conn, &guild_id, metric.name, value).map(|_| true)
+        } else {
+            remove_milestone_threshold(&conn, &guild_id, metric.name, value)
+        }
+    });
+    drop(conn); //Close database before sending
+    match result {
+        Ok(true) => {
+            println!("Milestone `{} {}` {} [Guild: {}] (!milestone)", metric.name, value, if action == "add" { "added" } else { "removed" }, guild_id); //Print success
+            send_simple_format(ctx, msg, locale.fill(if action == "add" { "milestone.added" } else { "milestone.removed" }, &[&describe(metric, value, locale)]).as_str()).await; //Send success
+        }
+        Ok(false) => send_simple_format(ctx, msg, format!("{} (`!milestone` `list`)", locale.text("milestone.not_found")).as_str()).await, //Send error
+        Err(e) => {
+            println!("Error updating milestones: {}", e); //Print error
+            send_simple_format(ctx, msg, locale.text("milestone.failed")).await; //Send error
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crossed_only_counts_the_change_past_the_milestone() {
+        let pp = metrics::find("pp").unwrap();
+        assert!(crossed(pp, 990.0, 1_010.0, 1_000.0));
+        assert!(crossed(pp, 990.0, 1_000.0, 1_000.0)); //Exactly at the milestone
+        assert!(!crossed(pp, 1_000.0, 1_010.0, 1_000.0)); //Already there
+        assert!(!crossed(pp, 980.0, 990.0, 1_000.0));
+        assert!(!crossed(pp, 1_010.0, 990.0, 1_000.0)); //Dropping back
+    }
+
+    #[test]
+    fn crossed_ranks_go_down_and_skip_unranked() {
+        let rank = metrics::find("rank").unwrap(); //Lower rank is better
+        assert!(crossed(rank, 1_020.0, 990.0, 1_000.0));
+        assert!(!crossed(rank, 990.0, 1_020.0, 1_000.0));
+        assert!(!crossed(rank, 0.0, 900.0, 1_000.0)); //Coming back from inactive
+        assert!(!crossed(rank, 1_020.0, 0.0, 1_000.0)); //Going inactive
+    }
+}
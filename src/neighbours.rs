@@ -0,0 +1,63 @@
+use crate::PlayerData; //PlayerData struct
+use crate::imports::*; //Imports
+use crate::datatweaks::{fetch_player_data, fetch_rank_range};
+use crate::format::{flag_emoji, profile_url};
+use crate::message::send_simple_format; //Function to send a simple embedded message
+use crate::locale::{self, Locale};
+use crate::theme; //Embed themes
+
+const RANGE: i64 = 5; //Amount of players shown above and below
+
+//Function to format one row (pp gap is from the player to the neighbour)
+fn format_row(neighbour: &PlayerData, data: &PlayerData, rank: i64, locale: Locale) -> String {
+    if neighbour.id == data.id { //The player themselves
+        return format!("**#{} {} - {}pp** ←", locale.integer(rank, false), neighbour.name, locale.decimal(neighbour.pp, 2));
+    }
+    let gap = neighbour.pp - data.pp;
+    format!("#{} [{}]({}) - {}pp (`{}{}pp`)", locale.integer(rank, false), neighbour.name, profile_url(&neighbour.id), locale.decimal(neighbour.pp, 2), if gap >= 0.0 { "+" } else { "" }, locale.decimal(gap, 2))
+}
+
+//Function to send the players ranked just above and below a player (scope is global or country)
+pub async fn send_neighbours(ctx: &Context, msg: &Message, player_id: &str, scope: &str) {
+    let locale = locale::for_message(msg); //Language and number format
+    let data = match fetch_player_data(player_id).await { //Fetch data from ScoreSaber API
+        Ok(data) => data,
+        Err(e) => {
+            println!("Error fetching data from API: {}", e); //Print error
+            send_simple_format(ctx, msg, format!("{} (`!neighbours` `player_id` `global|country [OPTIONAL]`)", locale.text("error.invalid_id")).as_str()).await; //Send error
+            return;
+        }
+    };
+    let country = scope == "country";
+    let rank = if country { data.countryRank } else { data.rank };
+    if rank <= 0 { //Inactive players are not in the ranking
+        send_simple_format(ctx, msg, locale.fill("neighbours.unranked", &[&data.name]).as_str()).await; //Send error
+        return;
+    }
+    let neighbours = match fetch_rank_range(rank - RANGE, rank + RANGE, if country { Some(data.country.as_str()) } else { None }).await { //Fetch the pages around the player
+        Ok(neighbours) => neighbours,
+        Err(e) => {
+            println!("Error fetching neighbours from API: {}", e); //Print error
+            send_simple_format(ctx, msg, locale.text("error.fetch_ranking")).await; //Send error
+            return;
+        }
+    };
+
+    //Making the description (one line per player)
+    let mut lines = Vec::new();
+    for neighbour in &neighbours {
+        lines.push(format_row(neighbour, &data, if country { neighbour.countryRank } else { neighbour.rank }, locale));
+    }
+    if !neighbours.iter().any(|neighbour| neighbour.id == data.id) { //Ranking moved since the player was fetched
+        lines.push(format_row(&data, &data, rank, locale));
+    }
+    let title = if country { locale.fill("neighbours.title_country", &[&flag_emoji(&data.country), &data.name]) } else { locale.fill("neighbours.title_global", &[&data.name]) };
+    let embed = CreateEmbed::new() //Make Embed
+        .color(theme::for_message(msg).color) //Set color to the theme
+        .title(title) //Set title
+        .description(lines.join("\n")); //Set description to the neighbours
+    if let Err(why) = msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await { // If sending message has error
+        println!("Error sending message: {:?}", why); // Print error
+    }
+    println!("Neighbours of `{}` sent to Discord (!neighbours {})", data.name, scope); //Print success
+}
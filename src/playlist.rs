@@ -0,0 +1,137 @@
+use crate::RankedMap; //RankedMap struct
+use crate::imports::*; //Imports
+use crate::datatweaks::fetch_bytes; //Function to download the cover image
+use base64::{engine::general_purpose::STANDARD, Engine}; //Playlist covers are stored as base64
+use image::ImageFormat;
+
+const AUTHOR: &str = "ScoreSaber Bot"; //Author shown in the game
+
+//Function to name a difficulty the way playlists do (None if the difficulty is unknown)
+fn playlist_difficulty(difficulty: i64) -> Option<&'static str> {
+    match difficulty {
+        1 => Some("easy"),
+        3 => Some("normal"),
+        5 => Some("hard"),
+        7 => Some("expert"),
+        9 => Some("expertPlus"),
+        _ => None,
+    }
+}
+
+//Function to find the characteristic of a game mode (SoloStandard is Standard)
+fn characteristic(game_mode: &str) -> &str {
+    game_mode.strip_prefix("Solo").unwrap_or(game_mode)
+}
+
+//Function to make a file name out of a title (letters, numbers and dashes)
+fn file_name(title: &str) -> String {
+    let name: String = title.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '-' }).collect();
+    let name = name.split('-').filter(|part| !part.is_empty()).collect::<Vec<&str>>().join("-");
+    format!("{}.bplist", if name.is_empty() { "playlist" } else { name.as_str() })
+}
+
+//Function to download the cover of the first map as a data URI
+async fn cover(maps: &[RankedMap]) -> Option<String> {
+    let url = &maps.iter().find(|map| !map.coverImage.is_empty())?.coverImage;
+    let bytes = match fetch_bytes(url).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            println!("Error fetching playlist cover: {}", e); //Print error
+            return None;
+        }
+    };
+    let mime = match image::guess_format(&bytes) {
+        Ok(ImageFormat::Jpeg) => "image/jpeg",
+        Ok(ImageFormat::Png) => "image/png",
+        _ => return None, //Not an image the game can show
+    };
+    Some(format!("data:{};base64,{}", mime, STANDARD.encode(bytes)))
+}
+
+//Function to make the playlist JSON (difficulties of the same song are grouped, order is kept)
+fn playlist_json(title: &str, description: &str, maps: &[RankedMap], image: Option<String>) -> serde_json::Value {
+    let mut hashes: Vec<&str> = Vec::new();
+    let mut songs: HashMap<&str, Vec<&RankedMap>> = HashMap::new();
+    for map in maps.iter().filter(|map| !map.songHash.is_empty()) {
+        if playlist_difficulty(map.difficulty).is_none() { //The game would load the wrong chart
+            println!("Unknown difficulty {} of leaderboard {}, left out of the playlist", map.difficulty, map.leaderboardId); //Print error
+            continue;
+        }
+        if !songs.contains_key(map.songHash.as_str()) {
+            hashes.push(&map.songHash);
+        }
+        songs.entry(&map.songHash).or_default().push(map);
+    }
+    let songs: Vec<serde_json::Value> = hashes.iter().map(|hash| {
+        let difficulties = &songs[hash];
+        json!({
+            "hash": hash,
+            "songName": difficulties[0].songName,
+            "levelAuthorName": difficulties[0].levelAuthorName,
+            "difficulties": difficulties.iter().filter_map(|map| playlist_difficulty(map.difficulty).map(|name| json!({
+                "characteristic": characteristic(&map.gameMode),
+                "name": name,
+            }))).collect::<Vec<serde_json::Value>>(),
+        })
+    }).collect();
+    json!({
+        "playlistTitle": title,
+        "playlistAuthor": AUTHOR,
+        "playlistDescription": description,
+        "image": image.unwrap_or_default(),
+        "songs": songs,
+    })
+}
+
+//Function to make a .bplist attachment out of a map list (None if there are no maps)
+pub async fn playlist_attachment(title: &str, description: &str, maps: &[RankedMap]) -> Option<CreateAttachment> {
+    if maps.is_empty() {
+        return None;
+    }
+    let playlist = playlist_json(title, description, maps, cover(maps).await);
+    match serde_json::to_vec_pretty(&playlist) {
+        Ok(bytes) => Some(CreateAttachment::bytes(bytes, file_name(title))),
+        Err(e) => {
+            println!("Error making playlist: {}", e); //Print error
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //Function to make a map of a song
+    fn map(leaderboard_id: i64, hash: &str, difficulty: i64) -> RankedMap {
+        RankedMap { leaderboardId: leaderboard_id, songHash: hash.to_string(), songName: format!("Song {}", hash), levelAuthorName: String::from("Mapper"), difficulty, gameMode: String::from("SoloStandard"), ..Default::default() }
+    }
+
+    #[test]
+    fn playlist_json_groups_difficulties_by_song() {
+        let maps = vec![map(1, "B", 9), map(2, "A", 7), map(3, "B", 5), map(4, "", 9)];
+        let playlist = playlist_json("Title", "Description", &maps, None);
+        assert_eq!(playlist["playlistTitle"], "Title");
+        assert_eq!(playlist["image"], "");
+        let songs = playlist["songs"].as_array().unwrap();
+        assert_eq!(songs.len(), 2); //Maps without a hash are left out
+        assert_eq!(songs[0]["hash"], "B"); //Order of the first difficulty is kept
+        assert_eq!(songs[0]["difficulties"], json!([{ "characteristic": "Standard", "name": "expertPlus" }, { "characteristic": "Standard", "name": "hard" }]));
+        assert_eq!(songs[1]["difficulties"][0]["name"], "expert");
+    }
+
+    #[test]
+    fn playlist_json_leaves_out_unknown_difficulties() {
+        let maps = vec![map(1, "A", 9), map(2, "A", 4), map(3, "B", 0)];
+        let playlist = playlist_json("Title", "Description", &maps, None);
+        let songs = playlist["songs"].as_array().unwrap();
+        assert_eq!(songs.len(), 1);
+        assert_eq!(songs[0]["difficulties"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn file_name_keeps_letters_and_numbers() {
+        assert_eq!(file_name("Snipe: Player #1"), "Snipe-Player-1.bplist");
+        assert_eq!(file_name("★★"), "playlist.bplist");
+    }
+}
@@ -0,0 +1,159 @@
+const WEIGHT: f64 = 0.965; //Every score is worth this much less than the one above it
+const PP_PER_STAR: f64 = 42.117208413; //Raw pp of one star at the 95% accuracy of the curve
+
+//ScoreSaber pp curve (accuracy, multiplier) from highest to lowest accuracy
+const CURVE: [(f64, f64); 37] = [
+    (1.0, 5.367394282890631), (0.9995, 5.019543595874787), (0.999, 4.715470646416203), (0.99825, 4.325027383589547),
+    (0.9975, 3.996793606763322), (0.99625, 3.5526145337555373), (0.995, 3.2022017597337955), (0.99375, 2.9190155639254955),
+    (0.9925, 2.685667856592722), (0.99125, 2.4902905794106913), (0.99, 2.324506282149922), (0.9875, 2.058947159052738),
+    (0.985, 1.8563887693647105), (0.9825, 1.697536248647543), (0.98, 1.5702410055532239), (0.9775, 1.4664726399289512),
+    (0.975, 1.3807102743105126), (0.9725, 1.3090333065057616), (0.97, 1.2485807759957321), (0.965, 1.1552120359501035),
+    (0.96, 1.0871883573850478), (0.955, 1.0388633331418984), (0.95, 1.0), (0.94, 0.9417362980580238),
+    (0.93, 0.9039994071865736), (0.92, 0.8728710341448851), (0.91, 0.8488375988124467), (0.9, 0.825756123560842),
+    (0.875, 0.7816934560296046), (0.85, 0.7462290664143185), (0.825, 0.7150465663454271), (0.8, 0.6872268862950283),
+    (0.75, 0.6451808210101443), (0.7, 0.6125565959114954), (0.65, 0.5866010012767576), (0.6, 0.18), (0.0, 0.0),
+];
+
+//Function to find the multiplier of an accuracy (0 to 1) on the pp curve (linear between points)
+pub fn curve(acc: f64) -> f64 {
+    let acc = acc.clamp(0.0, 1.0);
+    for pair in CURVE.windows(2) { //Find the two points around the accuracy
+        let (high, low) = (pair[0], pair[1]);
+        if acc >= low.0 {
+            return low.1 + (acc - low.0) / (high.0 - low.0) * (high.1 - low.1);
+        }
+    }
+    0.0
+}
+
+//Function to find the raw pp of a score on a map (accuracy from 0 to 1)
+pub fn raw_pp(stars: f64, acc: f64) -> f64 {
+    stars * PP_PER_STAR * curve(acc)
+}
+pub const MAX_SCORE_PAGES: i64 = 3; //Top scores fetched for pp math (300 scores, anything lower is worth almost nothing)
+
+//Function to find the weighted pp of a list of raw pp values (sorted from highest to lowest)
+pub fn weighted_total(pps: &[f64]) -> f64 {
+    pps.iter().enumerate().map(|(index, pp)| pp * WEIGHT.powi(index as i32)).sum()
+}
+
+//Function to insert a play into a sorted list of raw pp values
+fn insert_play(pps: &mut Vec<f64>, raw: f64) {
+    let position = pps.iter().position(|pp| *pp < raw).unwrap_or(pps.len());
+    pps.insert(position, raw);
+}
+
+//Function to find how much weighted pp a new play of some raw pp adds
+pub fn gain_from_play(pps: &[f64], raw: f64) -> f64 {
+    let mut with_play = pps.to_vec();
+    insert_play(&mut with_play, raw);
+    weighted_total(&with_play) - weighted_total(pps)
+}
+
+//Function to find how much weighted pp improving a score (old raw pp to new raw pp) adds
+pub fn gain_from_improvement(pps: &[f64], old: f64, new: f64) -> f64 {
+    let mut improved = pps.to_vec();
+    if let Some(position) = improved.iter().position(|pp| (*pp - old).abs() < 1e-9) { //Take the old score out
+        improved.remove(position);
+    }
+    insert_play(&mut improved, new);
+    weighted_total(&improved) - weighted_total(pps)
+}
+
+//Function to find how many new plays of some raw pp close a gap (None if it takes more than max)
+pub fn plays_needed(pps: &[f64], raw: f64, gap: f64, max: usize) -> Option<usize> {
+    let start = weighted_total(pps);
+    let mut with_plays = pps.to_vec();
+    for plays in 1..=max {
+        insert_play(&mut with_plays, raw);
+        if weighted_total(&with_plays) - start >= gap { //Gap closed
+            return Some(plays);
+        }
+    }
+    None
+}
+
+//Expected accuracy by star rating, fitted to a player's scores (acc = intercept + slope * stars)
+pub struct AccuracyProfile {
+    intercept: f64,
+    slope: f64,
+}
+
+impl AccuracyProfile {
+    //Function to fit a profile to (stars, accuracy) points (None if there are no points)
+    pub fn fit(points: &[(f64, f64)]) -> Option<AccuracyProfile> {
+        if points.is_empty() {
+            return None;
+        }
+        let count = points.len() as f64;
+        let mean_stars = points.iter().map(|(stars, _)| stars).sum::<f64>() / count;
+        let mean_acc = points.iter().map(|(_, acc)| acc).sum::<f64>() / count;
+        let variance: f64 = points.iter().map(|(stars, _)| (stars - mean_stars).powi(2)).sum();
+        let covariance: f64 = points.iter().map(|(stars, acc)| (stars - mean_stars) * (acc - mean_acc)).sum();
+        let slope = if variance > 0.0 { (covariance / variance).min(0.0) } else { 0.0 }; //Harder maps never get a better accuracy
+        Some(AccuracyProfile { intercept: mean_acc - slope * mean_stars, slope })
+    }
+
+    //Function to find the expected accuracy (0 to 1) on a map of some stars
+    pub fn expected(&self, stars: f64) -> f64 {
+        (self.intercept + self.slope * stars).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //Function to compare floats that went through some math
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn curve_hits_points_and_interpolates() {
+        assert!(close(curve(0.95), 1.0));
+        assert!(close(curve(1.0), 5.367394282890631));
+        assert!(close(curve(0.9525), 1.0194316665709492)); //Halfway between 0.95 and 0.955
+        assert!(close(curve(0.3), 0.09)); //Halfway between 0 and 0.6
+        assert!(close(curve(1.5), curve(1.0))); //Clamped
+        assert!(close(curve(-0.5), 0.0));
+        assert!(close(raw_pp(10.0, 0.95), 421.17208413));
+    }
+
+    #[test]
+    fn weighted_total_weighs_every_score_lower() {
+        assert!(close(weighted_total(&[]), 0.0));
+        assert!(close(weighted_total(&[100.0]), 100.0));
+        assert!(close(weighted_total(&[100.0, 100.0]), 196.5));
+        assert!(close(weighted_total(&[200.0, 100.0, 50.0]), 200.0 + 96.5 + 50.0 * 0.965 * 0.965));
+    }
+
+    #[test]
+    fn gains_insert_the_play_in_order() {
+        assert!(close(gain_from_play(&[100.0], 50.0), 48.25));
+        assert!(close(gain_from_play(&[100.0], 200.0), 196.5)); //New top play pushes the old one down
+        assert!(close(gain_from_improvement(&[100.0, 50.0], 50.0, 150.0), 98.25));
+        assert!(close(gain_from_improvement(&[100.0, 50.0], 100.0, 120.0), 20.0));
+    }
+
+    #[test]
+    fn plays_needed_counts_until_the_gap_is_closed() {
+        assert_eq!(plays_needed(&[], 100.0, 0.0, 5), Some(1));
+        assert_eq!(plays_needed(&[], 100.0, 150.0, 5), Some(2));
+        assert_eq!(plays_needed(&[], 100.0, 196.5, 5), Some(2));
+        assert_eq!(plays_needed(&[], 100.0, 1000.0, 5), None);
+    }
+
+    #[test]
+    fn profile_fits_a_line_through_the_points() {
+        assert!(AccuracyProfile::fit(&[]).is_none());
+        let profile = AccuracyProfile::fit(&[(5.0, 0.95), (10.0, 0.90)]).unwrap();
+        assert!(close(profile.expected(7.5), 0.925));
+        assert!(close(profile.expected(0.0), 1.0));
+        assert!(close(profile.expected(200.0), 0.0)); //Clamped
+        let single = AccuracyProfile::fit(&[(6.0, 0.93)]).unwrap();
+        assert!(close(single.expected(12.0), 0.93));
+        let rising = AccuracyProfile::fit(&[(5.0, 0.90), (10.0, 0.95)]).unwrap(); //Harder maps never get a better accuracy
+        assert!(close(rising.expected(10.0), 0.925));
+    }
+}
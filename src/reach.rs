@@ -0,0 +1,89 @@
+use crate::imports::*; //Imports
+use crate::datatweaks::{fetch_all_scores, fetch_player_data, fetch_rank_range};
+use crate::format::flag_emoji; //Function to turn a country code into a flag
+use crate::message::send_simple_format; //Function to send a simple embedded message
+use crate::locale::{self, Locale};
+use crate::pp::{self, MAX_SCORE_PAGES};
+use crate::theme; //Embed themes
+
+const MAX_PLAYS: usize = 50; //Estimates stop after this many plays
+const EXAMPLE_SCORES: [usize; 4] = [1, 5, 10, 25]; //Positions of the top scores used as examples if no raw pp is given
+
+//Function to describe how many plays of some raw pp close the gap
+fn describe_estimate(pps: &[f64], raw: f64, gap: f64, locale: Locale) -> String {
+    let raw_text = format!("{}pp", locale.decimal(raw, 2));
+    match pp::plays_needed(pps, raw, gap, MAX_PLAYS) {
+        Some(1) => locale.fill("reach.one_play", &[&raw_text]),
+        Some(plays) => locale.fill("reach.plays", &[&plays.to_string(), &raw_text]),
+        None => locale.fill("reach.too_many", &[&MAX_PLAYS.to_string(), &raw_text, &locale.decimal(pp::gain_from_play(pps, raw), 2)]),
+    }
+}
+
+//Function to send how much pp a player needs to reach a rank (raw is the pp of the plays to estimate with, scope is global or country)
+pub async fn send_reach(ctx: &Context, msg: &Message, player_id: &str, rank: &str, raw: &str, scope: &str) {
+    let locale = locale::for_message(msg); //Language and number format
+    let (rank, raw) = match (rank.parse::<i64>(), if raw.is_empty() { Ok(None) } else { raw.parse::<f64>().map(Some) }) { //Parse the parameters
+        (Ok(rank), Ok(raw)) if rank > 0 && raw.is_none_or(|raw| raw > 0.0) => (rank, raw),
+        _ => {
+            send_simple_format(ctx, msg, format!("{} (`!reach` `player_id` `rank` `raw_pp [OPTIONAL]` `global|country [OPTIONAL]`)", locale.text("reach.invalid")).as_str()).await; //Send error
+            return;
+        }
+    };
+    let data = match fetch_player_data(player_id).await { //Fetch data from ScoreSaber API
+        Ok(data) => data,
+        Err(e) => {
+            println!("Error fetching data from API: {}", e); //Print error
+            send_simple_format(ctx, msg, format!("{} (`!reach` `player_id` `rank`)", locale.text("error.invalid_id")).as_str()).await; //Send error
+            return;
+        }
+    };
+    let country = scope == "country";
+    let current = if country { data.countryRank } else { data.rank };
+    if current > 0 && current <= rank { //Already there
+        send_simple_format(ctx, msg, locale.fill("reach.already", &[&data.name, &locale.integer(current, false)]).as_str()).await; //Send message
+        return;
+    }
+    let holder = match fetch_rank_range(rank, rank, if country { Some(data.country.as_str()) } else { None }).await { //Player holding the rank now
+        Ok(players) => players.into_iter().next(),
+        Err(e) => {
+            println!("Error fetching ranking from API: {}", e); //Print error
+            None
+        }
+    };
+    let holder = match holder {
+        Some(holder) => holder,
+        None => {
+            send_simple_format(ctx, msg, locale.fill("reach.no_holder", &[&locale.integer(rank, false)]).as_str()).await; //Send error
+            return;
+        }
+    };
+    let scores = match fetch_all_scores(&data.id, "top", MAX_SCORE_PAGES).await { //Top scores for the weighting
+        Ok(scores) => scores,
+        Err(e) => {
+            println!("Error fetching scores from API: {}", e); //Print error
+            send_simple_format(ctx, msg, locale.text("error.fetch_scores")).await; //Send error
+            return;
+        }
+    };
+    let mut pps: Vec<f64> = scores.iter().map(|score| score.score.pp).filter(|pp| *pp > 0.0).collect();
+    pps.sort_by(|a, b| b.total_cmp(a));
+    let gap = holder.pp - data.pp;
+
+    //Plays to estimate with (the raw pp given, or some of the player's own top scores)
+    let raws: Vec<f64> = match raw {
+        Some(raw) => vec![raw],
+        None => EXAMPLE_SCORES.iter().filter_map(|position| pps.get(position - 1).copied()).collect(),
+    };
+    let estimates: Vec<String> = raws.iter().map(|raw| format!("- {}", describe_estimate(&pps, *raw, gap, locale))).collect();
+    let embed = CreateEmbed::new() //Make Embed
+        .color(theme::for_message(msg).color) //Set color to the theme
+        .title(if country { format!("{} → {} #{}", data.name, flag_emoji(&data.country), locale.integer(rank, false)) } else { format!("{} → #{}", data.name, locale.integer(rank, false)) }) //Set title
+        .field(locale.text("reach.holder"), format!("{} - {}pp", holder.name, locale.decimal(holder.pp, 2)), true)
+        .field(locale.text("reach.needed"), format!("{}pp", locale.decimal(gap.max(0.0), 2)), true)
+        .field(locale.text("reach.estimates"), if estimates.is_empty() { locale.text("reach.no_scores").to_string() } else { estimates.join("\n") }, false)
+        .footer(CreateEmbedFooter::new(locale.text("reach.footer")));
+    if let Err(why) = msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await { // If sending message has error
+        println!("Error sending message: {:?}", why); // Print error
+    }
+    println!("Reach #{} for `{}` sent to Discord (!reach {})", rank, data.name, scope); //Print success
+}
@@ -0,0 +1,218 @@
+use crate::CachedScore; //CachedScore struct
+use crate::RankedMap; //RankedMap struct
+use crate::imports::*; //Imports
+use crate::datatweaks::{create_db, fetch_cached_scores, fetch_player_data, fetch_ranked_maps_in_range, sync_score_cache, SCORES_PER_PAGE};
+use crate::message::send_simple_format; //Function to send a simple embedded message
+use crate::locale::{self, Locale};
+use crate::maps::format_map; //Function to format a map as one line
+use crate::playlist::playlist_attachment; //Function to export maps as a playlist
+use crate::pp::{self, AccuracyProfile, MAX_SCORE_PAGES};
+use crate::theme; //Embed themes
+
+const DEFAULT_COUNT: usize = 10; //Maps recommended if no count is given
+pub const MAX_COUNT: usize = 15; //Most maps that can be recommended at once (embeds have a length limit)
+const PROFILE_SCORES: usize = 50; //Top scores used for the default star range
+
+//One recommended map
+struct Recommendation {
+    map: RankedMap,
+    acc: f64, //Expected accuracy (0 to 1)
+    pp: f64, //Expected raw pp
+    old_pp: Option<f64>, //Raw pp of the current score (if played)
+    gain: f64, //Expected weighted pp gain
+}
+
+//Function to check if a parameter is an option of the command (a count or a star range) instead of a player
+pub fn is_option(param: &str) -> bool {
+    param.contains('-') || param.parse::<usize>().is_ok_and(|count| count <= MAX_COUNT)
+}
+
+//Function to parse a star range like 5-8
+fn parse_range(range: &str) -> Option<(f64, f64)> {
+    let (min, max) = range.split_once('-')?;
+    let (min, max) = (min.parse::<f64>().ok()?, max.parse::<f64>().ok()?);
+    if min < 0.0 || max < min {
+        return None;
+    }
+    Some((min, max))
+}
+
+//Function to parse the options (count and star range like 5-8, both optional, any order)
+fn parse_options(options: &[&str]) -> Option<(usize, Option<(f64, f64)>)> {
+    let mut count = DEFAULT_COUNT;
+    let mut range = None;
+    for option in options {
+        if option.contains('-') { //Star range
+            range = Some(parse_range(option)?);
+        } else { //Count
+            count = option.parse::<usize>().ok().filter(|count| (1..=MAX_COUNT).contains(count))?;
+        }
+    }
+    Some((count, range))
+}
+
+//Function to find the recommendations with the biggest expected weighted pp gain
+fn recommend(scores: &[CachedScore], maps: Vec<RankedMap>, profile: &AccuracyProfile, count: usize) -> Vec<Recommendation> {
+    let mut pps: Vec<f64> = scores.iter().map(|score| score.pp).filter(|pp| *pp > 0.0).collect();
+    pps.sort_by(|a, b| b.total_cmp(a));
+    let played: HashMap<i64, f64> = scores.iter().map(|score| (score.leaderboardId, score.pp)).collect(); //Every play, not only the top scores
+
+    let mut recommendations: Vec<Recommendation> = maps.into_iter().filter_map(|map| {
+        let acc = profile.expected(map.stars);
+        let pp = pp::raw_pp(map.stars, acc);
+        let old_pp = played.get(&map.leaderboardId).copied();
+        let gain = match old_pp {
+            Some(old) if old >= pp => return None, //Can't be improved at the expected accuracy
+            Some(old) => pp::gain_from_improvement(&pps, old, pp),
+            None => pp::gain_from_play(&pps, pp),
+        };
+        Some(Recommendation { map, acc, pp, old_pp, gain })
+    }).collect();
+    recommendations.sort_by(|a, b| b.gain.total_cmp(&a.gain));
+    recommendations.truncate(count);
+    recommendations
+}
+
+//Function to format one recommendation
+fn format_recommendation(position: usize, recommendation: &Recommendation, locale: Locale) -> String {
+    let acc = format!("{}{}", locale.decimal(recommendation.acc * 100.0, 2), locale.unit("%"));
+    let mut line = locale.fill("recommend.line", &[
+        &(position + 1).to_string(), &format_map(&recommendation.map, locale), &acc,
+        &locale.decimal(recommendation.pp, 2), &locale.decimal(recommendation.gain, 2)
+    ]);
+    if let Some(old) = recommendation.old_pp { //If the map was already played
+        line += &locale.fill("recommend.improves", &[&locale.decimal(old, 2)]);
+    }
+    line
+}
+
+//Function to send the maps with the biggest expected pp gain for a player (options are a count and a star range)
+pub async fn send_recommendations(ctx: &Context, msg: &Message, player_id: &str, options: &[&str]) {
+    let locale = locale::for_message(msg); //Language and number format
+    let (count, range) = match parse_options(options) {
+        Some(options) => options,
+        None => {
+            send_simple_format(ctx, msg, format!("{} (`!recommend` `player_id` `count (1-{}) [OPTIONAL]` `min-max stars [OPTIONAL]`)", locale.text("recommend.invalid"), MAX_COUNT).as_str()).await; //Send error
+            return;
+        }
+    };
+    let data = match fetch_player_data(player_id).await { //Fetch data from ScoreSaber API
+        Ok(data) => data,
+        Err(e) => {
+            println!("Error fetching data from API: {}", e); //Print error
+            send_simple_format(ctx, msg, format!("{} (`!recommend` `player_id`)", locale.text("error.invalid_id")).as_str()).await; //Send error
+            return;
+        }
+    };
+    if !sync_score_cache(&data.id).await { //Bring the score cache up to date
+        send_simple_format(ctx, msg, locale.text("error.fetch_scores")).await; //Send error
+        return;
+    }
+    let scores = {
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        create_db(&conn).and_then(|_| fetch_cached_scores(&conn, &data.id))
+    };
+    let mut scores = match scores {
+        Ok(scores) => scores,
+        Err(e) => {
+            println!("Error fetching cached scores: {}", e); //Print error
+            send_simple_format(ctx, msg, locale.text("error.load_scores")).await; //Send error
+            return;
+        }
+    };
+    scores.sort_by(|a, b| b.pp.total_cmp(&a.pp)); //Top scores first for the profile and the default range
+    let points: Vec<(f64, f64)> = scores.iter().take((MAX_SCORE_PAGES * SCORES_PER_PAGE) as usize).filter(|score| score.pp > 0.0).filter_map(|score| score.accuracy().map(|acc| (score.stars, acc))).collect();
+    let profile = match AccuracyProfile::fit(&points) {
+        Some(profile) => profile,
+        None => {
+            send_simple_format(ctx, msg, locale.fill("recommend.no_scores", &[&data.name]).as_str()).await; //Send error
+            return;
+        }
+    };
+    let (min, max) = range.unwrap_or_else(|| { //Default range is around the stars of the best scores
+        let stars: Vec<f64> = points.iter().take(PROFILE_SCORES).map(|(stars, _)| *stars).collect();
+        let average = stars.iter().sum::<f64>() / stars.len() as f64;
+        ((average - 1.5).max(0.0), stars.iter().cloned().fold(0.0, f64::max) + 0.5)
+    });
+    let maps = {
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        create_db(&conn).and_then(|_| fetch_ranked_maps_in_range(&conn, min, max)).unwrap_or_default()
+    };
+    if maps.is_empty() { //Catalogue not synced yet or nothing in range
+        send_simple_format(ctx, msg, locale.text("recommend.no_maps")).await; //Send error
+        return;
+    }
+
+    let recommendations = recommend(&scores, maps, &profile, count);
+    let lines: Vec<String> = recommendations.iter().enumerate().map(|(position, recommendation)| format_recommendation(position, recommendation, locale)).collect();
+    let embed = CreateEmbed::new() //Make Embed
+        .color(theme::for_message(msg).color) //Set color to the theme
+        .title(locale.fill("recommend.title", &[&data.name, &locale.decimal(min, 1), &locale.decimal(max, 1)])) //Set title
+        .description(if lines.is_empty() { locale.text("recommend.nothing").to_string() } else { lines.join("\n") })
+        .footer(CreateEmbedFooter::new(locale.text("recommend.footer")));
+    let maps: Vec<RankedMap> = recommendations.into_iter().map(|recommendation| recommendation.map).collect();
+    let mut message = CreateMessage::new().embed(embed);
+    if let Some(playlist) = playlist_attachment(&locale.fill("recommend.playlist", &[&data.name]), locale.text("recommend.playlist_description"), &maps).await {
+        message = message.add_file(playlist); //Playlist to load in game
+    }
+    if let Err(why) = msg.channel_id.send_message(&ctx.http, message).await { // If sending message has error
+        println!("Error sending message: {:?}", why); // Print error
+    }
+    println!("Recommendations for `{}` sent to Discord (!recommend)", data.name); //Print success
+}
+
+//Function to send the ranked maps in a star range a player has not played yet (with a playlist of all of them)
+pub async fn send_unplayed(ctx: &Context, msg: &Message, player_id: &str, range: &str) {
+    let locale = locale::for_message(msg); //Language and number format
+    let (min, max) = match parse_range(range) {
+        Some(range) => range,
+        None => {
+            send_simple_format(ctx, msg, format!("{} (`!unplayed` `player_id` `min-max stars`)", locale.text("unplayed.invalid")).as_str()).await; //Send error
+            return;
+        }
+    };
+    let data = match fetch_player_data(player_id).await { //Fetch data from ScoreSaber API
+        Ok(data) => data,
+        Err(e) => {
+            println!("Error fetching data from API: {}", e); //Print error
+            send_simple_format(ctx, msg, format!("{} (`!unplayed` `player_id` `min-max stars`)", locale.text("error.invalid_id")).as_str()).await; //Send error
+            return;
+        }
+    };
+    if !sync_score_cache(&data.id).await { //Bring the score cache up to date
+        send_simple_format(ctx, msg, locale.text("error.fetch_scores")).await; //Send error
+        return;
+    }
+    let maps = {
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        create_db(&conn).and_then(|_| {
+            let played: std::collections::HashSet<i64> = fetch_cached_scores(&conn, &data.id)?.iter().map(|score| score.leaderboardId).collect();
+            Ok(fetch_ranked_maps_in_range(&conn, min, max)?.into_iter().filter(|map| !played.contains(&map.leaderboardId)).collect::<Vec<RankedMap>>())
+        })
+    };
+    let mut maps = match maps {
+        Ok(maps) => maps,
+        Err(e) => {
+            println!("Error fetching unplayed maps from database: {}", e); //Print error
+            send_simple_format(ctx, msg, locale.text("unplayed.failed")).await; //Send error
+            return;
+        }
+    };
+    maps.sort_by(|a, b| a.stars.total_cmp(&b.stars)); //Easiest first
+
+    let title = locale.fill("unplayed.title", &[&data.name, &locale.decimal(min, 1), &locale.decimal(max, 1)]);
+    let lines: Vec<String> = maps.iter().take(MAX_COUNT).map(|map| format_map(map, locale)).collect();
+    let embed = CreateEmbed::new() //Make Embed
+        .color(theme::for_message(msg).color) //Set color to the theme
+        .title(&title) //Set title
+        .description(if lines.is_empty() { locale.text("unplayed.all_played").to_string() } else { lines.join("\n") })
+        .footer(CreateEmbedFooter::new(locale.fill("unplayed.footer", &[&locale.integer(maps.len() as i64, false)])));
+    let mut message = CreateMessage::new().embed(embed);
+    if let Some(playlist) = playlist_attachment(&title, locale.text("unplayed.playlist_description"), &maps).await {
+        message = message.add_file(playlist); //Playlist to load in game
+    }
+    if let Err(why) = msg.channel_id.send_message(&ctx.http, message).await { // If sending message has error
+        println!("Error sending message: {:?}", why); // Print error
+    }
+    println!("Unplayed maps of `{}` sent to Discord (!unplayed)", data.name); //Print success
+}
@@ -0,0 +1,209 @@
+use crate::PlayerData; //PlayerData struct
+use crate::Changes; //Changes struct
+use crate::imports::*; //Imports
+use crate::datatweaks::{add_rival, create_db, fetch_player_data, fetch_player_data_from_db, fetch_rivalries, fetch_rivals, insert_player_data, remove_rival};
+use crate::locale; //Language and number format
+use crate::message::{send_announcement, send_simple_format};
+use crate::theme; //Embed themes
+
+//One player passing another player
+struct Overtake {
+    passer: String,
+    passed: String,
+    kind: &'static str, //Global or country rank
+    rank: i64, //Rank of the passer after passing
+    guild: String, //Guild the rivalry was declared in
+}
+
+//Function to check if pp went from behind to ahead of another player's pp (Some(true) if passed, Some(false) if got passed)
+fn passed(old_pp: f64, new_pp: f64, other_old_pp: f64, other_new_pp: f64) -> Option<bool> {
+    if old_pp <= other_old_pp && new_pp > other_new_pp { //Was behind (or tied) and is now ahead
+        Some(true)
+    } else if old_pp > other_old_pp && new_pp <= other_new_pp { //Was ahead and is now behind (or tied)
+        Some(false)
+    } else {
+        None
+    }
+}
+
+//Function to find the rivals a refreshed player passed (or got passed by) using the pp change of both players
+async fn find_overtakes(data: &PlayerData, changes: &Changes) -> Result<Vec<Overtake>> {
+    let change = match changes.get("pp") { //Ranks come from pp so nobody is passed without a pp change
+        Some(change) => change,
+        None => return Ok(Vec::new()),
+    };
+    let rivalries = {
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        create_db(&conn)?; //Create database if not exists
+        let mut rivalries = Vec::new();
+        for (other_id, guild) in fetch_rivalries(&conn, &data.id)? { //Loop through every rivalry of the player
+            match fetch_player_data_from_db(&conn, &other_id) { //Stored data of the other player (their pp before now)
+                Ok(other) => rivalries.push((other, guild)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => continue, //Rival was never stored
+                Err(e) => return Err(e),
+            }
+        }
+        rivalries
+    };
+
+    let mut overtakes = Vec::new();
+    for (stored, guild) in rivalries {
+        let other = match fetch_player_data(&stored.id).await { //Current data of the other player
+            Ok(other) => other,
+            Err(e) => {
+                println!("Error fetching rival from API: {}", e); //Print error
+                continue;
+            }
+        };
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        insert_player_data(&conn, &other, false)?; //Keep the stored rival current for the next check
+        drop(conn); //Close database before the next fetch
+        if data.rank <= 0 || other.rank <= 0 { //Inactive players have a rank of 0
+            continue;
+        }
+        let direction = match passed(change.old, change.new, stored.pp, other.pp) {
+            Some(direction) => direction,
+            None => continue,
+        };
+        for (kind, rank, other_rank) in [("global", data.rank, other.rank), ("country", data.countryRank, other.countryRank)] { //Announce both ranks
+            if kind == "country" && data.country != other.country { //Country ranks only compare within a country
+                continue;
+            }
+            if direction {
+                overtakes.push(Overtake { passer: data.name.clone(), passed: other.name.clone(), kind, rank, guild: guild.clone() });
+            } else {
+                overtakes.push(Overtake { passer: other.name.clone(), passed: data.name.clone(), kind, rank: other_rank, guild: guild.clone() });
+            }
+        }
+    }
+    Ok(overtakes)
+}
+
+//Function to post a callout for every rival passed during a refresh
+pub async fn announce_overtakes(ctx: &Context, msg: &Message, data: &PlayerData, changes: &Changes) {
+    let overtakes = match find_overtakes(data, changes).await {
+        Ok(overtakes) => overtakes,
+        Err(e) => {
+            println!("Error checking rivals: {}", e); //Print error
+            return;
+        }
+    };
+    let theme = theme::for_message(msg); //Colors of the embeds
+    let locale = locale::for_message(msg); //Language and number format
+    for overtake in overtakes { //Loop through every overtake
+        let embed = CreateEmbed::new() //Make Embed
+            .color(theme.accent) //Set color to the accent of the theme
+            .title(locale.text("rival.overtake_title")) //Set title
+            .description(locale.fill(&format!("rival.overtake_{}", overtake.kind), &[&overtake.passer, &overtake.passed, &locale.integer(overtake.rank, false)]));
+        let fallback = if msg.guild_id.map(|guild_id| guild_id.to_string()) == Some(overtake.guild.clone()) { Some(msg.channel_id) } else { None }; //Only fall back to a channel of the same guild
+        send_announcement(ctx, &overtake.guild, fallback, embed).await;
+        println!("{} passed rival {} in {} rank", overtake.passer, overtake.passed, overtake.kind); //Print overtake
+    }
+}
+
+//Function to make sure a rival is stored in the database (fetches them from ScoreSaber)
+async fn store_rival(rival_id: &str) -> Option<PlayerData> {
+    let data = match fetch_player_data(rival_id).await { //Fetch data from ScoreSaber API
+        Ok(data) => data,
+        Err(e) => {
+            println!("Error fetching rival from API: {}", e); //Print error
+            return None;
+        }
+    };
+    let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+    let new_user = matches!(fetch_player_data_from_db(&conn, rival_id), Err(rusqlite::Error::QueryReturnedNoRows)); //If the rival is not stored yet
+    if let Err(e) = insert_player_data(&conn, &data, new_user) { //Store the rival
+        println!("Error inserting rival into database: {}", e); //Print error
+        return None;
+    }
+    Some(data)
+}
+
+//Function to react to the rival command (add, remove, list)
+pub async fn rival_command(ctx: &Context, msg: &Message, action: &str, rival_id: &str, linked_id: &str) {
+    let locale = locale::for_message(msg); //Language and number format
+    if linked_id.is_empty() { //Rivals belong to linked players
+        send_simple_format(ctx, msg, format!("{} (`!link` `player_id`)", locale.text("remind.link_first")).as_str()).await; //Send error
+        return;
+    }
+    match action {
+        "add" => {
+            let guild_id = match msg.guild_id { //Rivals are announced inside of a server
+                Some(guild_id) => guild_id.to_string(),
+                None => {
+                    send_simple_format(ctx, msg, locale.text("rival.guild_only")).await; //Send error
+                    return;
+                }
+            };
+            if rival_id == linked_id { //Can't be your own rival
+                send_simple_format(ctx, msg, locale.text("rival.yourself")).await; //Send error
+                return;
+            }
+            match store_rival(rival_id).await {
+                Some(rival) => {
+                    let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+                    let result = add_rival(&conn, linked_id, rival_id, &guild_id);
+                    drop(conn); //Close database before sending
+                    if let Err(e) = result { //If failed to store the rival
+                        println!("Error adding rival: {}", e); //Print error
+                        send_simple_format(ctx, msg, locale.text("rival.add_failed")).await; //Send error
+                    } else {
+                        println!("Player `{}` added rival `{}` (!rival add)", linked_id, rival_id); //Print success
+                        send_simple_format(ctx, msg, locale.fill("rival.added", &[&rival.name, &locale.integer(rival.rank, false)]).as_str()).await; //Send success
+                    }
+                }
+                None => send_simple_format(ctx, msg, format!("{} (`!rival` `add` `player_id`)", locale.text("error.invalid_id")).as_str()).await, //Send error
+            }
+        }
+        "remove" => {
+            let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+            let result = create_db(&conn).and_then(|_| remove_rival(&conn, linked_id, rival_id));
+            drop(conn); //Close database before sending
+            match result {
+                Ok(true) => send_simple_format(ctx, msg, locale.fill("rival.removed", &[rival_id]).as_str()).await, //Send success
+                Ok(false) => send_simple_format(ctx, msg, format!("{} (`!rival` `list`)", locale.fill("rival.not_found", &[rival_id])).as_str()).await, //Send error
+                Err(e) => {
+                    println!("Error removing rival: {}", e); //Print error
+                    send_simple_format(ctx, msg, locale.text("rival.remove_failed")).await; //Send error
+                }
+            }
+        }
+        _ => { //List
+            let rivals = {
+                let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+                create_db(&conn).and_then(|_| fetch_rivals(&conn, linked_id)).unwrap_or_default().iter()
+                    .filter_map(|rival_id| fetch_player_data_from_db(&conn, rival_id).ok())
+                    .map(|rival| format!("**{}** (`{}`) - #{}", rival.name, rival.id, locale.integer(rival.rank, false)))
+                    .collect::<Vec<String>>()
+            };
+            if rivals.is_empty() { //If player has no rivals
+                send_simple_format(ctx, msg, format!("{} (`!rival` `add` `player_id`)", locale.text("rival.none")).as_str()).await; //Send error
+            } else {
+                send_simple_format(ctx, msg, format!("**{}**\n{}", locale.text("rival.list"), rivals.join("\n")).as_str()).await; //Send list
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passed_detects_overtakes_both_ways() {
+        assert_eq!(passed(100.0, 110.0, 105.0, 105.0), Some(true));
+        assert_eq!(passed(105.0, 106.0, 105.0, 105.0), Some(true)); //Tied counts as behind
+        assert_eq!(passed(110.0, 100.0, 105.0, 105.0), Some(false));
+        assert_eq!(passed(110.0, 105.0, 105.0, 105.0), Some(false));
+        assert_eq!(passed(100.0, 105.0, 105.0, 105.0), None);
+        assert_eq!(passed(100.0, 101.0, 105.0, 105.0), None);
+        assert_eq!(passed(110.0, 120.0, 105.0, 105.0), None);
+    }
+
+    #[test]
+    fn passed_uses_the_current_pp_of_the_rival() {
+        assert_eq!(passed(100.0, 110.0, 105.0, 115.0), None); //Rival climbed too and stays ahead
+        assert_eq!(passed(110.0, 112.0, 105.0, 115.0), Some(false)); //Rival climbed past
+        assert_eq!(passed(100.0, 101.0, 105.0, 95.0), Some(true)); //Rival dropped below
+    }
+}
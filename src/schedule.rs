@@ -0,0 +1,100 @@
+use crate::imports::*; //Imports
+use crate::datatweaks::{create_db, current_time, fetch_announce_channels, fetch_guild_discords, fetch_guild_player_ids, refresh_players, set_weekly_posted};
+use crate::leaderboard::weekly_climbers; //Function to make the weekly climbers embed
+use crate::maps::{announce_map_changes, sync_maps};
+use crate::activity::send_inactivity_reminders; //Function to remind inactive players
+
+//Static variables
+pub static mut SCHEDULING: bool = false; //Shows if the background jobs have been started
+
+const CHECK_EVERY: u64 = 3600; //Background jobs are checked every hour
+pub const WEEK: i64 = 604_800; //Seconds in a week
+const MONDAY: i64 = 345_600; //Seconds from the unix epoch (a Thursday) to the first Monday
+
+//Function to find the start of the week (Monday 00:00 UTC) a time is in
+pub fn week_start(time: i64) -> i64 {
+    (time - MONDAY).div_euclid(WEEK) * WEEK + MONDAY
+}
+
+//Function to start the background jobs (only once, even if the bot reconnects)
+pub fn start(ctx: Context) {
+    unsafe { //Using pub static variables
+        if SCHEDULING { //If already started
+            return;
+        }
+        SCHEDULING = true; //Jobs are now running
+    }
+    let maps_ctx = ctx.clone(); //Each loop needs its own context
+    tokio::spawn(async move {
+        loop { //Loop forever
+            post_weekly_climbers(&ctx).await; //Post the weekly climbers if a new week started
+            send_inactivity_reminders(&ctx).await; //Remind opted in players that stopped playing
+            sleep(Duration::from_secs(CHECK_EVERY)).await; //Waits before checking again
+        }
+    });
+    tokio::spawn(async move { //Map syncs can take a while so they get their own loop
+        loop { //Loop forever
+            if let Some(changes) = sync_maps().await { //Sync the ranked map catalogue
+                announce_map_changes(&maps_ctx, &changes).await; //Announce new, re-rated and unranked maps
+            }
+            sleep(Duration::from_secs(CHECK_EVERY)).await; //Waits before syncing again
+        }
+    });
+}
+
+//Function to find the guilds that have not had the climbers of last week posted (guild, channel)
+fn due_weekly_climbers(week: i64) -> Result<Vec<(String, String)>> {
+    let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+    create_db(&conn)?; //Create database if not exists
+    let channels = fetch_announce_channels(&conn)?;
+    Ok(channels.into_iter().filter(|(_, _, posted)| *posted < week).map(|(guild, channel, _)| (guild, channel)).collect())
+}
+
+//Function to post the weekly biggest climbers in every guild with an announcement channel
+async fn post_weekly_climbers(ctx: &Context) {
+    let week = week_start(current_time()); //Start of the current week
+    let due = match due_weekly_climbers(week) {
+        Ok(due) => due,
+        Err(e) => {
+            println!("Error fetching guild settings from database: {}", e); //Print error
+            return;
+        }
+    };
+
+    for (guild_id, channel_id) in due { //Loop through every guild that is due
+        let discords = match guild_id.parse::<u64>() { //Members of the guild
+            Ok(id) => match fetch_guild_discords(ctx, GuildId::new(id)).await {
+                Ok(discords) => discords,
+                Err(e) => {
+                    println!("Error fetching guild members [Guild: {}]: {}", guild_id, e); //Print error and try again next hour
+                    continue;
+                }
+            },
+            Err(_) => continue, //Invalid stored guild
+        };
+        let player_ids = { //Refresh the linked players so the new week starts from their current stats
+            let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+            fetch_guild_player_ids(&conn, &discords).unwrap_or_default()
+        };
+        refresh_players(player_ids.into_iter().map(|(player_id, _)| player_id).collect()).await;
+
+        match weekly_climbers(&guild_id, &discords, week - WEEK, week) { //Make the embed for last week
+            Ok(Some(embed)) => {
+                if let Ok(channel) = channel_id.parse::<u64>() { //If the stored channel is valid
+                    if let Err(why) = ChannelId::new(channel).send_message(&ctx.http, CreateMessage::new().embed(embed)).await { // If sending message has error
+                        println!("Error sending weekly climbers: {:?}", why); // Print error
+                    } else {
+                        println!("Weekly climbers sent to Discord [Guild: {}]", guild_id); // Prints if successful
+                    }
+                }
+            }
+            Ok(None) => println!("No history for the weekly climbers [Guild: {}]", guild_id), //Nothing to post
+            Err(e) => println!("Error computing weekly climbers: {}", e), //Print error
+        }
+
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        if let Err(e) = set_weekly_posted(&conn, &guild_id, week) { //Remember the week was posted
+            println!("Error updating guild settings: {}", e); //Print error
+        }
+    }
+}
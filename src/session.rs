@@ -0,0 +1,107 @@
+use crate::{PlayerScore, Snapshot}; //PlayerScore and Snapshot structs
+use crate::imports::*; //Imports
+use crate::datatweaks::{current_time, fetch_player_data, fetch_player_scores, SCORES_PER_PAGE};
+use crate::format::difficulty_name; //Function to name a difficulty
+use crate::locale::{self, Locale};
+use crate::theme::{self, Theme};
+
+pub const SESSION_GAP: i64 = 20; //Minutes without a new score that end a session (if none are given)
+pub const MAX_SESSION_GAP: i64 = 180;
+
+//A finished play session
+pub struct Session {
+    before: Snapshot, //Stats from before the first score
+    after: Snapshot, //Stats after the last score
+    scores: Vec<PlayerScore>, //Scores set in the session (oldest first)
+}
+
+//Follows the recent scores of a tracked player to find play sessions
+pub struct SessionTracker {
+    name: String, //Name of the player (for the recap)
+    gap: i64, //Seconds without a new score that end a session
+    last_seen: i64, //Time of the newest score seen
+    baseline: Snapshot, //Stats from before the next session (taken at the start and after every session)
+    scores: Vec<PlayerScore>, //Scores of the session in progress (oldest first)
+}
+
+//Function to find when a score was set (seconds since unix epoch)
+fn score_time(score: &PlayerScore) -> i64 {
+    DateTime::parse_from_rfc3339(&score.score.timeSet).map(|time| time.timestamp()).unwrap_or(0)
+}
+
+impl SessionTracker {
+    //Function to start following a player (scores set before now are not part of any session)
+    pub async fn start(player_id: &str, gap_minutes: i64) -> Result<SessionTracker, Error> {
+        let data = fetch_player_data(player_id).await?;
+        let recent = fetch_player_scores(&data.id, "recent", 1, 1).await?;
+        Ok(SessionTracker {
+            name: data.name.clone(),
+            gap: gap_minutes * 60,
+            last_seen: recent.playerScores.first().map(score_time).unwrap_or(0),
+            baseline: data.snapshot(),
+            scores: Vec::new(),
+        })
+    }
+
+    //Function to check for new scores (returns the session once the player stopped playing)
+    pub async fn poll(&mut self, player_id: &str) -> Result<Option<Session>, Error> {
+        let recent = fetch_player_scores(player_id, "recent", 1, SCORES_PER_PAGE).await?;
+        let mut new: Vec<PlayerScore> = recent.playerScores.into_iter().filter(|score| score_time(score) > self.last_seen).collect();
+        new.reverse(); //Recent scores come newest first
+        if let Some(newest) = new.last() {
+            self.last_seen = score_time(newest);
+        }
+        self.scores.extend(new);
+
+        if self.scores.is_empty() { //Not playing (the baseline from the start or the last session is the stats before the next one)
+            return Ok(None);
+        }
+        if current_time() - self.last_seen < self.gap { //Still playing
+            return Ok(None);
+        }
+        let after = fetch_player_data(player_id).await?.snapshot();
+        let before = std::mem::replace(&mut self.baseline, after.clone()); //Stats after this session are the baseline of the next one
+        Ok(Some(Session { before, after, scores: std::mem::take(&mut self.scores) }))
+    }
+
+    //Function to make the recap embed of a finished session
+    fn recap_embed(&self, session: &Session, theme: &Theme, locale: Locale) -> CreateEmbed {
+        let first = session.scores.first().map(score_time).unwrap_or(0);
+        let last = session.scores.last().map(score_time).unwrap_or(0);
+        let minutes = (last - first) / 60;
+        let plays = (session.after.totalPlayCount - session.before.totalPlayCount).max(session.scores.len() as i64); //Play count also counts plays that weren't a PB
+        let pp = session.after.pp - session.before.pp;
+        let rank = session.before.rank - session.after.rank; //Positive when climbing
+
+        let mut accuracies: Vec<(f64, &PlayerScore)> = session.scores.iter().filter_map(|score| score.accuracy().map(|acc| (acc * 100.0, score))).collect();
+        accuracies.sort_by(|a, b| b.0.total_cmp(&a.0));
+        let describe = |(acc, score): &(f64, &PlayerScore)| {
+            locale.fill("session.score", &[&format!("{}{}", locale.decimal(*acc, 2), locale.unit("%")), &score.leaderboard.songName, difficulty_name(score.leaderboard.difficulty.difficulty)])
+        };
+
+        let mut embed = CreateEmbed::new() //Make Embed
+            .color(theme.color) //Set color to the theme
+            .title(locale.fill("session.title", &[&self.name])) //Set title
+            .field(locale.text("session.plays"), locale.integer(plays, false), true)
+            .field(locale.text("session.pbs"), locale.integer(session.scores.len() as i64, false), true)
+            .field(locale.text("session.length"), if minutes >= 60 { format!("{}h {:02}m", minutes / 60, minutes % 60) } else { format!("{}m", minutes) }, true)
+            .field(locale.text("session.pp"), format!("{}{}pp", if pp >= 0.0 { "+" } else { "" }, locale.decimal(pp, 2)), true)
+            .field(locale.text("session.rank"), format!("#{} → #{} ({})", locale.integer(session.before.rank, false), locale.integer(session.after.rank, false), locale.integer(rank, true)), true);
+        if let (Some(best), Some(worst)) = (accuracies.first(), accuracies.last()) { //Maps without a max score have no accuracy
+            embed = embed
+                .field(locale.text("session.best"), describe(best), false)
+                .field(locale.text("session.worst"), describe(worst), false);
+        }
+        embed.footer(CreateEmbedFooter::new(locale.fill("session.footer", &[&(self.gap / 60).to_string()])))
+    }
+}
+
+//Function to send the recap of a finished session
+pub async fn send_recap(ctx: &Context, msg: &Message, tracker: &SessionTracker, session: &Session) {
+    let embed = tracker.recap_embed(session, &theme::for_message(msg), locale::for_message(msg));
+    if let Err(why) = msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await { // If sending message has error
+        println!("Error sending message: {:?}", why); // Print error
+    } else {
+        println!("Session recap of `{}` sent to Discord (!track)", tracker.name); //Print success
+    }
+}
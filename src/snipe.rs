@@ -0,0 +1,131 @@
+use crate::CachedScore; //CachedScore struct
+use crate::RankedMap; //RankedMap struct
+use crate::imports::*; //Imports
+use crate::datatweaks::{create_db, fetch_cached_scores, fetch_map, fetch_player_data, sync_score_cache};
+use crate::format::difficulty_name; //Function to name a difficulty
+use crate::message::send_simple_format; //Function to send a simple embedded message
+use crate::locale::{self, Locale};
+use crate::maps::leaderboard_url; //Function to link the leaderboard of a map
+use crate::playlist::playlist_attachment; //Function to export maps as a playlist
+use crate::pp;
+use crate::theme; //Embed themes
+
+const MAX_SNIPES: usize = 15; //Maps shown in the snipe list
+
+//One map to snipe
+struct Snipe {
+    target: CachedScore, //Score to beat
+    mine: Option<CachedScore>, //Own score on the map (if played)
+    gain: f64, //Weighted pp gained by matching the target's accuracy
+}
+
+//Function to find the ranked maps where the target has a score and the player doesn't (or beats the player), biggest pp gain first
+fn find_snipes(mine: &[CachedScore], target: Vec<CachedScore>) -> Vec<Snipe> {
+    let mut pps: Vec<f64> = mine.iter().map(|score| score.pp).filter(|pp| *pp > 0.0).collect();
+    pps.sort_by(|a, b| b.total_cmp(a));
+    let own: HashMap<i64, &CachedScore> = mine.iter().map(|score| (score.leaderboardId, score)).collect();
+
+    let mut snipes: Vec<Snipe> = target.into_iter().filter(|score| score.pp > 0.0).filter_map(|score| { //Only ranked scores give pp
+        let mine = own.get(&score.leaderboardId).map(|mine| (*mine).clone());
+        let gain = match &mine {
+            Some(mine) if mine.modifiedScore >= score.modifiedScore => return None, //Already ahead (leaderboards rank by the score with modifiers)
+            Some(mine) => pp::gain_from_improvement(&pps, mine.pp, score.pp),
+            None => pp::gain_from_play(&pps, score.pp),
+        };
+        Some(Snipe { target: score, mine, gain })
+    }).collect();
+    snipes.sort_by(|a, b| b.gain.total_cmp(&a.gain));
+    snipes
+}
+
+//Function to format one snipe
+fn format_snipe(position: usize, snipe: &Snipe, locale: Locale) -> String {
+    let percent = |score: &CachedScore| format!("{}{}", locale.decimal(score.accuracy().unwrap_or(0.0) * 100.0, 2), locale.unit("%"));
+    let mine = match &snipe.mine {
+        Some(mine) => locale.fill("snipe.yours", &[&percent(mine)]),
+        None => locale.text("snipe.unplayed").to_string(),
+    };
+    locale.fill("snipe.line", &[
+        &(position + 1).to_string(), &snipe.target.songName, &leaderboard_url(snipe.target.leaderboardId), &snipe.target.levelAuthorName, difficulty_name(snipe.target.difficulty),
+        &locale.decimal(snipe.target.stars, 2), &percent(&snipe.target), &mine, &locale.decimal(snipe.gain, 2)
+    ])
+}
+
+//Function to send the maps where a target beats a player, sorted by the pp the player gains by matching them
+pub async fn send_snipes(ctx: &Context, msg: &Message, player_id: &str, target_id: &str) {
+    let locale = locale::for_message(msg); //Language and number format
+    let (player, target) = match (fetch_player_data(player_id).await, fetch_player_data(target_id).await) { //Fetch both from ScoreSaber API
+        (Ok(player), Ok(target)) => (player, target),
+        _ => {
+            send_simple_format(ctx, msg, format!("{} (`!snipe` `player_id` `target_id`)", locale.text("error.invalid_id")).as_str()).await; //Send error
+            return;
+        }
+    };
+    if player.id == target.id { //Can't snipe yourself
+        send_simple_format(ctx, msg, locale.text("snipe.yourself")).await; //Send error
+        return;
+    }
+    if !sync_score_cache(&player.id).await || !sync_score_cache(&target.id).await { //Bring both score caches up to date
+        send_simple_format(ctx, msg, locale.text("error.fetch_scores")).await; //Send error
+        return;
+    }
+    let scores = {
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        create_db(&conn).and_then(|_| Ok((fetch_cached_scores(&conn, &player.id)?, fetch_cached_scores(&conn, &target.id)?)))
+    };
+    let (mine, theirs) = match scores {
+        Ok(scores) => scores,
+        Err(e) => {
+            println!("Error fetching cached scores: {}", e); //Print error
+            send_simple_format(ctx, msg, locale.text("error.load_scores")).await; //Send error
+            return;
+        }
+    };
+
+    let snipes = find_snipes(&mine, theirs);
+    let unplayed = snipes.iter().filter(|snipe| snipe.mine.is_none()).count();
+    let lines: Vec<String> = snipes.iter().take(MAX_SNIPES).enumerate().map(|(position, snipe)| format_snipe(position, snipe, locale)).collect();
+    let embed = CreateEmbed::new() //Make Embed
+        .color(theme::for_message(msg).color) //Set color to the theme
+        .title(locale.fill("snipe.title", &[&player.name, &target.name])) //Set title
+        .description(if lines.is_empty() { locale.fill("snipe.none", &[&target.name]) } else { lines.join("\n") })
+        .footer(CreateEmbedFooter::new(locale.fill("snipe.footer", &[&(snipes.len() - unplayed).to_string(), &unplayed.to_string()])));
+    let maps: Vec<RankedMap> = { //Every snipe from the map catalogue (for the song hashes)
+        let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+        snipes.iter().filter_map(|snipe| fetch_map(&conn, snipe.target.leaderboardId).ok().flatten()).collect()
+    };
+    let mut message = CreateMessage::new().embed(embed);
+    if let Some(playlist) = playlist_attachment(&locale.fill("snipe.playlist", &[&target.name]), &locale.fill("snipe.playlist_description", &[&target.name, &player.name]), &maps).await {
+        message = message.add_file(playlist); //Playlist to load in game
+    }
+    if let Err(why) = msg.channel_id.send_message(&ctx.http, message).await { // If sending message has error
+        println!("Error sending message: {:?}", why); // Print error
+    }
+    println!("Snipe list of `{}` against `{}` sent to Discord (!snipe)", player.name, target.name); //Print success
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //Function to make a cached score on a map
+    fn score(leaderboard_id: i64, base_score: i64, modified_score: i64, pp: f64) -> CachedScore {
+        CachedScore { leaderboardId: leaderboard_id, baseScore: base_score, modifiedScore: modified_score, pp, maxScore: 1_000_000, ..Default::default() }
+    }
+
+    #[test]
+    fn find_snipes_compares_scores_with_modifiers() {
+        let mine = vec![score(1, 900_000, 900_000, 300.0), score(2, 950_000, 855_000, 280.0)];
+        let target = vec![
+            score(1, 920_000, 880_000, 310.0), //Higher base score but behind with modifiers
+            score(2, 900_000, 900_000, 270.0), //Lower base score but ahead with modifiers
+            score(3, 800_000, 800_000, 200.0), //Unplayed
+            score(4, 990_000, 990_000, 0.0), //Unranked
+        ];
+        let snipes = find_snipes(&mine, target);
+        let maps: Vec<i64> = snipes.iter().map(|snipe| snipe.target.leaderboardId).collect();
+        assert_eq!(maps.len(), 2);
+        assert!(maps.contains(&2) && maps.contains(&3));
+        assert!(snipes.windows(2).all(|pair| pair[0].gain >= pair[1].gain)); //Biggest gain first
+    }
+}
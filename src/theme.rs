@@ -0,0 +1,133 @@
+use crate::imports::*; //Imports
+use crate::datatweaks::{create_db, delete_theme_settings, fetch_theme_settings, save_theme_settings};
+use crate::message::{can_manage_guild, send_simple_format}; //Functions to check server permissions and send a simple embedded message
+use crate::locale; //Language of the replies
+
+//Layouts of the stats embed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Layout {
+    Compact, //Everything in a single field
+    Detailed, //Every stat in its own row
+    Grid, //Two stats per row
+}
+
+//Colors and layout applied to every embed
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    pub color: u32, //Color of the main embeds
+    pub accent: u32, //Color of secondary embeds (goals and announcements)
+    pub layout: Layout,
+}
+
+//Theme settings stored for a user or a guild (None falls back to the next level)
+#[derive(Debug, Default)]
+pub struct ThemeSettings {
+    pub color: Option<u32>,
+    pub accent: Option<u32>,
+    pub layout: Option<String>,
+}
+
+//Default theme when neither the user nor the guild picked one
+const DEFAULT_THEME: Theme = Theme { color: 0, accent: 0, layout: Layout::Grid };
+
+//Function to turn a layout name into a layout
+fn parse_layout(layout: &str) -> Option<Layout> {
+    match layout.to_lowercase().as_str() {
+        "compact" => Some(Layout::Compact),
+        "detailed" => Some(Layout::Detailed),
+        "grid" => Some(Layout::Grid),
+        _ => None,
+    }
+}
+
+//Function to turn a hex color (#550000 or 550000) into a number
+fn parse_color(color: &str) -> Option<u32> {
+    let hex = color.trim_start_matches('#');
+    if hex.len() != 6 { //Colors need six digits
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok()
+}
+
+//Function to find the theme of a user in a guild (user settings, then guild settings, then the default)
+pub fn resolve(discord_id: Option<&str>, guild_id: Option<&str>) -> Theme {
+    let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database (tables are created at startup)
+    let user = discord_id.and_then(|discord_id| fetch_theme_settings(&conn, "user", discord_id).ok().flatten()).unwrap_or_default();
+    let guild = guild_id.and_then(|guild_id| fetch_theme_settings(&conn, "guild", guild_id).ok().flatten()).unwrap_or_default();
+    Theme {
+        color: user.color.or(guild.color).unwrap_or(DEFAULT_THEME.color),
+        accent: user.accent.or(guild.accent).unwrap_or(DEFAULT_THEME.accent),
+        layout: user.layout.or(guild.layout).and_then(|layout| parse_layout(&layout)).unwrap_or(DEFAULT_THEME.layout),
+    }
+}
+
+//Function to find the theme for replying to a message
+pub fn for_message(msg: &Message) -> Theme {
+    resolve(Some(&msg.author.name), msg.guild_id.map(|guild_id| guild_id.to_string()).as_deref())
+}
+
+//Function to react to the theme command (color, accent, layout, reset, show) for the user or the server
+pub async fn theme_command(ctx: &Context, msg: &Message, parts: &[&str]) {
+    let locale = locale::for_message(msg); //Language of the replies
+    //Settings apply to the user unless the first word is "server"
+    let (scope, owner, parts) = match parts.first() {
+        Some(&"server") => match msg.guild_id {
+            Some(guild_id) => ("guild", guild_id.to_string(), &parts[1..]),
+            None => {
+                send_simple_format(ctx, msg, locale.text("theme.guild_only")).await; //Send error
+                return;
+            }
+        },
+        _ => ("user", msg.author.name.clone(), parts),
+    };
+    let action = parts.first().copied().unwrap_or("show");
+    if scope == "guild" && action != "show" && !can_manage_guild(ctx, msg).await { //Only managers can change the settings of the server
+        send_simple_format(ctx, msg, locale.text("error.manage_guild")).await; //Send error
+        return;
+    }
+    let value = parts.get(1).copied().unwrap_or("");
+
+    let conn = Connection::open("player_data.db").expect("Failed to open database"); //Set up connection for database
+    let mut settings = match create_db(&conn).and_then(|_| fetch_theme_settings(&conn, scope, &owner)) {
+        Ok(settings) => settings.unwrap_or_default(),
+        Err(e) => {
+            drop(conn); //Close database before sending
+            println!("Error fetching theme: {}", e); //Print error
+            send_simple_format(ctx, msg, locale.text("theme.load_failed")).await; //Send error
+            return;
+        }
+    };
+    let reply = match action {
+        "color" | "accent" => match parse_color(value) {
+            Some(color) => {
+                if action == "color" { settings.color = Some(color) } else { settings.accent = Some(color) }
+                save_theme_settings(&conn, scope, &owner, &settings).map(|_| locale.fill(if action == "color" { "theme.color_set" } else { "theme.accent_set" }, &[&format!("#{:06X}", color)]))
+            }
+            None => Ok(locale.text("theme.invalid_color").to_string()),
+        },
+        "layout" => match parse_layout(value) {
+            Some(_) => {
+                settings.layout = Some(value.to_lowercase());
+                save_theme_settings(&conn, scope, &owner, &settings).map(|_| locale.fill("theme.layout_set", &[&value.to_lowercase()]))
+            }
+            None => Ok(locale.text("theme.invalid_layout").to_string()),
+        },
+        "reset" => delete_theme_settings(&conn, scope, &owner).map(|_| locale.text("theme.reset").to_string()),
+        _ => Ok(locale.fill("theme.show", &[ //Show
+            &settings.color.map(|color| format!("#{:06X}", color)).unwrap_or(locale.text("theme.default").to_string()),
+            &settings.accent.map(|color| format!("#{:06X}", color)).unwrap_or(locale.text("theme.default").to_string()),
+            &settings.layout.clone().unwrap_or(locale.text("theme.default").to_string()),
+        ])),
+    };
+    drop(conn); //Close database before sending
+    match reply {
+        Ok(reply) => {
+            println!("Theme `{}` for {} `{}` (!theme)", action, scope, owner); //Print success
+            send_simple_format(ctx, msg, &reply).await; //Send reply
+        }
+        Err(e) => {
+            println!("Error saving theme: {}", e); //Print error
+            send_simple_format(ctx, msg, locale.text("theme.save_failed")).await; //Send error
+        }
+    }
+}